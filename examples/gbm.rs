@@ -0,0 +1,215 @@
+extern crate egl_wrapper;
+
+extern crate gl;
+
+mod utils;
+
+use std::env;
+use std::fs::{File, OpenOptions};
+use std::os::raw::{c_int, c_void};
+use std::os::unix::io::AsRawFd;
+
+use egl_wrapper::config::attribute::NativeRenderable;
+use egl_wrapper::display::{Display, DisplayType};
+use egl_wrapper::platform::EXTPlatform;
+use egl_wrapper::surface::window::WindowSurfaceAttributeListBuilder;
+use egl_wrapper::EGLHandle;
+
+#[allow(non_camel_case_types)]
+enum gbm_device {}
+
+#[allow(non_camel_case_types)]
+enum gbm_surface {}
+
+#[allow(non_camel_case_types)]
+enum gbm_bo {}
+
+/// DRM fourcc code 'AR24'
+const GBM_FORMAT_ARGB8888: u32 = 0x3432_5241;
+const GBM_BO_USE_RENDERING: u32 = 1 << 2;
+
+const WIDTH: u32 = 640;
+const HEIGHT: u32 = 480;
+
+#[link(name = "gbm")]
+extern "C" {
+    fn gbm_create_device(fd: c_int) -> *mut gbm_device;
+    fn gbm_device_destroy(device: *mut gbm_device);
+    fn gbm_surface_create(
+        device: *mut gbm_device,
+        width: u32,
+        height: u32,
+        format: u32,
+        flags: u32,
+    ) -> *mut gbm_surface;
+    fn gbm_surface_destroy(surface: *mut gbm_surface);
+    fn gbm_surface_lock_front_buffer(surface: *mut gbm_surface) -> *mut gbm_bo;
+    fn gbm_surface_release_buffer(surface: *mut gbm_surface, bo: *mut gbm_bo);
+    fn gbm_bo_get_width(bo: *mut gbm_bo) -> u32;
+    fn gbm_bo_get_height(bo: *mut gbm_bo) -> u32;
+}
+
+/// `gbm_device` which is destroyed before the DRM device file is closed.
+#[derive(Debug)]
+struct GbmDevice {
+    raw_device: *mut gbm_device,
+    _file: File,
+}
+
+impl GbmDevice {
+    fn new(path: &str) -> Option<Self> {
+        let file = OpenOptions::new().read(true).write(true).open(path).ok()?;
+        let raw_device = unsafe { gbm_create_device(file.as_raw_fd()) };
+
+        if raw_device.is_null() {
+            return None;
+        }
+
+        Some(GbmDevice {
+            raw_device,
+            _file: file,
+        })
+    }
+}
+
+impl Drop for GbmDevice {
+    fn drop(&mut self) {
+        unsafe { gbm_device_destroy(self.raw_device) }
+    }
+}
+
+/// `gbm_surface` owned by the EGL window surface.
+#[derive(Debug)]
+struct GbmSurface {
+    raw_surface: *mut gbm_surface,
+}
+
+impl Drop for GbmSurface {
+    fn drop(&mut self) {
+        unsafe { gbm_surface_destroy(self.raw_surface) }
+    }
+}
+
+// Render one frame to a gbm_surface. Path to the DRM device can be given
+// as the first argument.
+fn main() {
+    let device_path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| "/dev/dri/renderD128".to_string());
+
+    let gbm_device = GbmDevice::new(&device_path).expect("gbm_device creation failed");
+    let raw_gbm_device = gbm_device.raw_device;
+
+    let egl_handle = EGLHandle::load().unwrap();
+
+    // Create EGLDisplay. Display owns the gbm_device.
+
+    let display: Display<EXTPlatform<GbmDevice>> = unsafe {
+        egl_handle
+            .display_builder()
+            .build_gbm_platform_display(raw_gbm_device as *mut c_void, gbm_device, None)
+            .expect("error")
+    };
+
+    println!("egl: version {:?}", display.egl_version());
+
+    // Find EGLConfig with the same format as the gbm_surface.
+
+    let (config_window, opengl_context_builder) = {
+        let config = utils::search_configs(&display)
+            .into_iter()
+            .find(|config| {
+                config.native_visual_id().unwrap() == Some(GBM_FORMAT_ARGB8888 as i32)
+            })
+            .expect("no config with format GBM_FORMAT_ARGB8888");
+
+        let config_window = display.window_surface(&config).unwrap().unwrap();
+        let opengl_context_builder = display.opengl_context_builder(&config).unwrap().unwrap();
+
+        (config_window, opengl_context_builder)
+    };
+
+    // Create gbm_surface and EGLSurface
+
+    let gbm_surface = unsafe {
+        let raw_surface = gbm_surface_create(
+            raw_gbm_device,
+            WIDTH,
+            HEIGHT,
+            GBM_FORMAT_ARGB8888,
+            GBM_BO_USE_RENDERING,
+        );
+
+        if raw_surface.is_null() {
+            panic!("gbm_surface creation failed");
+        }
+
+        GbmSurface { raw_surface }
+    };
+
+    let attributes = WindowSurfaceAttributeListBuilder::new().build();
+    let egl_window_surface = unsafe {
+        let raw_surface = gbm_surface.raw_surface as *mut c_void;
+
+        display.display_handle()
+            .platform()
+            .get_gbm_window_surface(gbm_surface, raw_surface, config_window, attributes)
+            .unwrap()
+    };
+
+    // Create OpenGL context
+
+    let context = display
+        .build_opengl_context(opengl_context_builder)
+        .unwrap();
+
+    let mut current_context = context.make_current(egl_window_surface).unwrap();
+
+    // Load OpenGL functions
+
+    {
+        let function_loader = current_context
+            .context()
+            .display()
+            .function_loader()
+            .unwrap();
+        gl::load_with(|s| function_loader.get_proc_address(s).unwrap());
+    }
+
+    utils::print_opengl_info();
+
+    // Clear color buffer and swap buffers
+
+    unsafe {
+        gl::ClearColor(0.0, 0.5, 0.8, 1.0);
+        gl::Clear(gl::COLOR_BUFFER_BIT);
+    }
+
+    current_context = current_context.swap_buffers().unwrap();
+
+    // Front buffer must be released before the next buffer swap.
+
+    unsafe {
+        let raw_surface = current_context.surface().optional_native_window_handle().raw_surface;
+        let bo = gbm_surface_lock_front_buffer(raw_surface);
+
+        if bo.is_null() {
+            panic!("gbm_surface_lock_front_buffer failed");
+        }
+
+        println!("front buffer: {}x{}", gbm_bo_get_width(bo), gbm_bo_get_height(bo));
+
+        gbm_surface_release_buffer(raw_surface, bo);
+    }
+
+    // EGL destroys a current surface only after it is released, so release
+    // the context before the gbm_surface and gbm_device are destroyed.
+
+    let (context, egl_window_surface) = current_context
+        .make_not_current()
+        .map_err(|(_, error)| error)
+        .unwrap();
+
+    drop(egl_window_surface);
+    drop(context);
+}
@@ -0,0 +1,147 @@
+extern crate egl_wrapper;
+
+extern crate gl;
+extern crate winit;
+
+mod utils;
+
+use winit::{Event, EventsLoop, WindowBuilder, WindowEvent};
+use winit::os::unix::{EventsLoopExt, WindowExt};
+
+use egl_wrapper::display::{Display, DisplayType};
+use egl_wrapper::platform::{EXTPlatform, EXTPlatformType};
+use egl_wrapper::surface::window::WindowSurfaceAttributeListBuilder;
+use egl_wrapper::wayland::{WaylandEGL, WaylandEGLWindow, wl_surface};
+use egl_wrapper::EGLHandle;
+
+const WIDTH: u32 = 640;
+const HEIGHT: u32 = 480;
+
+fn main() {
+    let egl_handle = EGLHandle::load().unwrap();
+    let wayland_egl = WaylandEGL::load().unwrap();
+
+    let mut events_loop = EventsLoop::new();
+
+    if !events_loop.is_wayland() {
+        println!("Wayland is required");
+
+        return;
+    }
+
+    let window = WindowBuilder::new()
+        .with_title("egl-wrapper wayland example")
+        .with_dimensions(WIDTH, HEIGHT)
+        .build(&events_loop)
+        .unwrap();
+
+    // Create EGLDisplay
+
+    let wl_display = window.get_wayland_display().unwrap();
+
+    let display_builder = egl_handle.display_builder();
+
+    let display: Display<EXTPlatform<()>> = unsafe {
+        display_builder
+            .build_ext_platform_base_display(EXTPlatformType::Wayland, wl_display, (), None)
+            .expect("error")
+    };
+
+    println!("egl: version {:?}", display.egl_version());
+
+    // Find EGLConfig
+
+    let (config_window, opengl_context_builder) = {
+        let config = utils::search_configs(&display).into_iter().next().unwrap();
+        let config_window = display.window_surface(&config).unwrap().unwrap();
+        let opengl_context_builder = display.opengl_context_builder(&config).unwrap().unwrap();
+
+        (config_window, opengl_context_builder)
+    };
+
+    // Create wl_egl_window and EGLSurface
+
+    let egl_window = unsafe {
+        let surface = window.get_wayland_surface().unwrap() as *mut wl_surface;
+        WaylandEGLWindow::new(&wayland_egl, surface, WIDTH as i32, HEIGHT as i32).unwrap()
+    };
+
+    let attributes = WindowSurfaceAttributeListBuilder::new().build();
+    let egl_window_surface = unsafe {
+        display.display_handle()
+            .platform()
+            .get_wayland_window_surface(egl_window, config_window, attributes)
+            .unwrap()
+    };
+
+    // Create OpenGL context
+
+    let context = display
+        .build_opengl_context(opengl_context_builder)
+        .unwrap();
+
+    let mut current_context = context.make_current(egl_window_surface).unwrap();
+
+    // Load OpenGL functions
+
+    {
+        let function_loader = current_context
+            .context()
+            .display()
+            .function_loader()
+            .unwrap();
+        gl::load_with(|s| function_loader.get_proc_address(s).unwrap());
+    }
+
+    utils::print_opengl_info();
+
+    // Handle window events and draw
+
+    loop {
+        let mut new_size = None;
+        let mut closed = false;
+
+        events_loop.poll_events(|event| match event {
+            Event::WindowEvent { event: WindowEvent::Resized(width, height), .. } => {
+                new_size = Some((width as i32, height as i32));
+            }
+            Event::WindowEvent { event: WindowEvent::Closed, .. } => closed = true,
+            _ => (),
+        });
+
+        if closed {
+            break;
+        }
+
+        // Wayland compositor does not resize the window buffer, so
+        // wl_egl_window must be resized before the next buffer swap.
+        if let Some((width, height)) = new_size {
+            current_context
+                .surface_mut()
+                .optional_native_window_handle_mut()
+                .resize(width, height, 0, 0);
+
+            unsafe {
+                gl::Viewport(0, 0, width, height);
+            }
+        }
+
+        unsafe {
+            gl::ClearColor(0.0, 0.5, 0.8, 1.0);
+            gl::Clear(gl::COLOR_BUFFER_BIT);
+        }
+
+        current_context = current_context.swap_buffers().unwrap();
+    }
+
+    // EGL destroys a current surface only after it is released, so release
+    // the context before wl_egl_window is destroyed.
+
+    let (context, egl_window_surface) = current_context
+        .make_not_current()
+        .map_err(|(_, error)| error)
+        .unwrap();
+
+    drop(egl_window_surface);
+    drop(context);
+}
@@ -0,0 +1,82 @@
+extern crate egl_wrapper;
+
+extern crate gl;
+extern crate x11_wrapper;
+
+mod utils;
+
+use std::env;
+use std::os::raw::c_void;
+use std::sync::Arc;
+
+use x11_wrapper::XlibHandle;
+use x11_wrapper::core::display::DisplayHandle;
+use x11_wrapper::x11::xlib;
+
+use egl_wrapper::display::{Display, DisplayType};
+use egl_wrapper::platform::{EXTPlatform, EXTPlatformAttributeListBuilder, EXTPlatformType};
+use egl_wrapper::utils::UnsignedInteger;
+use egl_wrapper::EGLHandle;
+
+// Create EGLDisplay for one screen of the X11 display with extension
+// EGL_EXT_platform_x11. Screen number can be given as the first argument,
+// otherwise displays are created for all screens.
+fn main() {
+    let egl_handle = EGLHandle::load().unwrap();
+
+    let display_builder = egl_handle.display_builder();
+
+    if !display_builder.ext_platform_x11() {
+        println!("EGL extension EGL_EXT_platform_x11 is not supported");
+
+        return;
+    }
+
+    let xlib_handle = XlibHandle::initialize_xlib().unwrap();
+    let x11_display = xlib_handle.create_display().unwrap();
+
+    let screen_count = unsafe { xlib::XScreenCount(x11_display.raw_display()) };
+
+    println!("X11 screen count: {}", screen_count);
+
+    let screens = match env::args().nth(1) {
+        Some(screen) => {
+            let screen: i32 = screen.parse().expect("screen number is not valid");
+
+            if screen < 0 || screen >= screen_count {
+                println!("screen {} does not exist", screen);
+
+                return;
+            }
+
+            screen..screen + 1
+        }
+        None => 0..screen_count,
+    };
+
+    for screen in screens {
+        let mut attributes = EXTPlatformAttributeListBuilder::new();
+        attributes.x11_screen(UnsignedInteger::new(screen));
+
+        let display: Display<EXTPlatform<Arc<DisplayHandle>>> = unsafe {
+            egl_handle
+                .display_builder()
+                .build_ext_platform_base_display(
+                    EXTPlatformType::X11,
+                    x11_display.raw_display() as *mut c_void,
+                    x11_display.display_handle().clone(),
+                    Some(attributes.build()),
+                )
+                .expect("error")
+        };
+
+        println!("screen {}:", screen);
+        println!("  egl: version {:?}", display.egl_version());
+        println!("  vendor: {:?}", display.vendor().unwrap());
+        println!("  config count: {}", display.configs().unwrap().count());
+        println!(
+            "  config search results count: {}",
+            utils::search_configs(&display).count()
+        );
+    }
+}
@@ -0,0 +1,324 @@
+extern crate egl_wrapper;
+
+extern crate gl;
+
+mod utils;
+
+use std::env;
+use std::os::raw::{c_char, c_int, c_uint, c_void};
+use std::ptr;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use egl_wrapper::config::attribute::NativeRenderable;
+use egl_wrapper::display::{Display, DisplayType};
+use egl_wrapper::platform::{xcb_window_t, EXTPlatform, EXTPlatformAttributeListBuilder,
+                            EXTPlatformType};
+use egl_wrapper::surface::window::WindowSurfaceAttributeListBuilder;
+use egl_wrapper::utils::UnsignedInteger;
+use egl_wrapper::EGLHandle;
+
+#[allow(non_camel_case_types)]
+enum xcb_connection_t {}
+
+#[allow(non_camel_case_types)]
+enum xcb_setup_t {}
+
+#[allow(non_camel_case_types, dead_code)]
+#[repr(C)]
+struct xcb_screen_t {
+    root: xcb_window_t,
+    default_colormap: u32,
+    white_pixel: u32,
+    black_pixel: u32,
+    current_input_masks: u32,
+    width_in_pixels: u16,
+    height_in_pixels: u16,
+    width_in_millimeters: u16,
+    height_in_millimeters: u16,
+    min_installed_maps: u16,
+    max_installed_maps: u16,
+    root_visual: u32,
+    backing_stores: u8,
+    save_unders: u8,
+    root_depth: u8,
+    allowed_depths_len: u8,
+}
+
+#[allow(non_camel_case_types, dead_code)]
+#[repr(C)]
+struct xcb_screen_iterator_t {
+    data: *mut xcb_screen_t,
+    rem: c_int,
+    index: c_int,
+}
+
+#[allow(non_camel_case_types, dead_code)]
+#[repr(C)]
+struct xcb_void_cookie_t {
+    sequence: c_uint,
+}
+
+const XCB_COPY_FROM_PARENT: u8 = 0;
+const XCB_WINDOW_CLASS_INPUT_OUTPUT: u16 = 1;
+
+const WIDTH: u16 = 640;
+const HEIGHT: u16 = 480;
+
+#[link(name = "xcb")]
+extern "C" {
+    fn xcb_connect(display_name: *const c_char, screen: *mut c_int) -> *mut xcb_connection_t;
+    fn xcb_connection_has_error(connection: *mut xcb_connection_t) -> c_int;
+    fn xcb_disconnect(connection: *mut xcb_connection_t);
+    fn xcb_get_setup(connection: *mut xcb_connection_t) -> *const xcb_setup_t;
+    fn xcb_setup_roots_length(setup: *const xcb_setup_t) -> c_int;
+    fn xcb_setup_roots_iterator(setup: *const xcb_setup_t) -> xcb_screen_iterator_t;
+    fn xcb_screen_next(iterator: *mut xcb_screen_iterator_t);
+    fn xcb_generate_id(connection: *mut xcb_connection_t) -> u32;
+    fn xcb_create_window(
+        connection: *mut xcb_connection_t,
+        depth: u8,
+        window: xcb_window_t,
+        parent: xcb_window_t,
+        x: i16,
+        y: i16,
+        width: u16,
+        height: u16,
+        border_width: u16,
+        class: u16,
+        visual: u32,
+        value_mask: u32,
+        value_list: *const c_void,
+    ) -> xcb_void_cookie_t;
+    fn xcb_destroy_window(
+        connection: *mut xcb_connection_t,
+        window: xcb_window_t,
+    ) -> xcb_void_cookie_t;
+    fn xcb_map_window(
+        connection: *mut xcb_connection_t,
+        window: xcb_window_t,
+    ) -> xcb_void_cookie_t;
+    fn xcb_flush(connection: *mut xcb_connection_t) -> c_int;
+}
+
+/// XCB connection which is closed when dropped.
+#[derive(Debug)]
+struct XcbConnection {
+    raw_connection: *mut xcb_connection_t,
+    default_screen: c_int,
+}
+
+impl XcbConnection {
+    fn connect() -> Option<Self> {
+        let mut default_screen = 0;
+        let raw_connection = unsafe { xcb_connect(ptr::null(), &mut default_screen) };
+
+        if unsafe { xcb_connection_has_error(raw_connection) } != 0 {
+            unsafe { xcb_disconnect(raw_connection) };
+            return None;
+        }
+
+        Some(XcbConnection {
+            raw_connection,
+            default_screen,
+        })
+    }
+
+    fn screen_count(&self) -> c_int {
+        unsafe { xcb_setup_roots_length(xcb_get_setup(self.raw_connection)) }
+    }
+
+    /// Returns root window and root visual of the screen.
+    fn screen_root(&self, screen: c_int) -> (xcb_window_t, u32) {
+        unsafe {
+            let mut iterator = xcb_setup_roots_iterator(xcb_get_setup(self.raw_connection));
+
+            for _ in 0..screen {
+                xcb_screen_next(&mut iterator);
+            }
+
+            ((*iterator.data).root, (*iterator.data).root_visual)
+        }
+    }
+}
+
+impl Drop for XcbConnection {
+    fn drop(&mut self) {
+        unsafe { xcb_disconnect(self.raw_connection) }
+    }
+}
+
+/// XCB window which is destroyed when dropped.
+#[derive(Debug)]
+struct XcbWindow {
+    connection: Arc<XcbConnection>,
+    window: xcb_window_t,
+}
+
+impl Drop for XcbWindow {
+    fn drop(&mut self) {
+        unsafe {
+            xcb_destroy_window(self.connection.raw_connection, self.window);
+            xcb_flush(self.connection.raw_connection);
+        }
+    }
+}
+
+// Create EGLDisplay for one screen of the XCB connection with extension
+// EGL_EXT_platform_xcb and draw to a window on that screen. Screen number
+// can be given as the first argument, otherwise the default screen is used.
+fn main() {
+    let egl_handle = EGLHandle::load().unwrap();
+
+    let display_builder = egl_handle.display_builder();
+
+    if !display_builder.ext_platform_xcb() {
+        println!("EGL extension EGL_EXT_platform_xcb is not supported");
+
+        return;
+    }
+
+    let connection = Arc::new(XcbConnection::connect().expect("XCB connection failed"));
+    let screen_count = connection.screen_count();
+
+    println!("XCB screen count: {}", screen_count);
+
+    let screen = match env::args().nth(1) {
+        Some(screen) => screen.parse().expect("screen number is not valid"),
+        None => connection.default_screen,
+    };
+
+    if screen < 0 || screen >= screen_count {
+        println!("screen {} does not exist", screen);
+
+        return;
+    }
+
+    // Create EGLDisplay for the screen. Display keeps the connection alive.
+
+    let mut attributes = EXTPlatformAttributeListBuilder::new();
+    attributes.xcb_screen(UnsignedInteger::new(screen));
+
+    let display: Display<EXTPlatform<Arc<XcbConnection>>> = unsafe {
+        display_builder
+            .build_ext_platform_base_display(
+                EXTPlatformType::XCB,
+                connection.raw_connection as *mut c_void,
+                connection.clone(),
+                Some(attributes.build()),
+            )
+            .expect("error")
+    };
+
+    println!("screen {}:", screen);
+    println!("  egl: version {:?}", display.egl_version());
+    println!("  vendor: {:?}", display.vendor().unwrap());
+
+    // Find EGLConfig with the root visual of the screen, so that the window
+    // can use the depth and visual of its parent.
+
+    let (root, root_visual) = connection.screen_root(screen);
+
+    let (config_window, opengl_context_builder) = {
+        let config = display
+            .configs()
+            .unwrap()
+            .into_iter()
+            .find(|config| {
+                config.window_config().unwrap() && config.opengl_config().unwrap() &&
+                    config.native_visual_id().unwrap() == Some(root_visual as i32)
+            })
+            .expect("no OpenGL config with the root visual of the screen");
+
+        let config_window = display.window_surface(&config).unwrap().unwrap();
+        let opengl_context_builder = display.opengl_context_builder(&config).unwrap().unwrap();
+
+        (config_window, opengl_context_builder)
+    };
+
+    // Create XCB window
+
+    let xcb_window = unsafe {
+        let raw_connection = connection.raw_connection;
+        let window = xcb_generate_id(raw_connection);
+
+        xcb_create_window(
+            raw_connection,
+            XCB_COPY_FROM_PARENT,
+            window,
+            root,
+            0,
+            0,
+            WIDTH,
+            HEIGHT,
+            0,
+            XCB_WINDOW_CLASS_INPUT_OUTPUT,
+            root_visual,
+            0,
+            ptr::null(),
+        );
+        xcb_map_window(raw_connection, window);
+        xcb_flush(raw_connection);
+
+        XcbWindow {
+            connection: connection.clone(),
+            window,
+        }
+    };
+
+    // Create EGLSurface
+
+    let attributes = WindowSurfaceAttributeListBuilder::new().build();
+    let egl_window_surface = unsafe {
+        let window = xcb_window.window;
+
+        display.display_handle()
+            .platform()
+            .get_xcb_window_surface(xcb_window, window, config_window, attributes)
+            .unwrap()
+    };
+
+    // Create OpenGL context
+
+    let context = display
+        .build_opengl_context(opengl_context_builder)
+        .unwrap();
+
+    let mut current_context = context.make_current(egl_window_surface).unwrap();
+
+    // Load OpenGL functions
+
+    {
+        let function_loader = current_context
+            .context()
+            .display()
+            .function_loader()
+            .unwrap();
+        gl::load_with(|s| function_loader.get_proc_address(s).unwrap());
+    }
+
+    utils::print_opengl_info();
+
+    // Clear color buffer and swap buffers
+
+    unsafe {
+        gl::ClearColor(0.0, 0.5, 0.8, 1.0);
+        gl::Clear(gl::COLOR_BUFFER_BIT);
+    }
+
+    current_context = current_context.swap_buffers().unwrap();
+
+    thread::sleep(Duration::from_secs(2));
+
+    // EGL destroys a current surface only after it is released, so release
+    // the context before the XCB window is destroyed.
+
+    let (context, egl_window_surface) = current_context
+        .make_not_current()
+        .map_err(|(_, error)| error)
+        .unwrap();
+
+    drop(egl_window_surface);
+    drop(context);
+}
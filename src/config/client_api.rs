@@ -31,5 +31,8 @@ macro_rules! config_type {
 
 config_type!(ConfigOpenGL);
 config_type!(ConfigOpenGLES);
+config_type!(ConfigOpenVG);
 
 config_type!(ConfigWindow);
+config_type!(ConfigPbuffer);
+config_type!(ConfigPixmap);
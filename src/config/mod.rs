@@ -44,8 +44,16 @@ impl<P: Platform> DisplayConfig<P> {
     pub fn egl_handle(&self) -> &EGLHandle {
         self.display_handle.egl_handle()
     }
+
+    pub fn display_handle(&self) -> &Arc<DisplayHandle<P>> {
+        &self.display_handle
+    }
 }
 
+// EGLConfig is immutable handle.
+unsafe impl<P: Platform + Send + Sync> Send for DisplayConfig<P> {}
+unsafe impl<P: Platform + Send + Sync> Sync for DisplayConfig<P> {}
+
 /// Config query results.
 pub struct Configs<'a, D: DisplayType + 'a> {
     display: &'a D,
@@ -113,6 +121,16 @@ impl<'a, D: DisplayType + 'a> Config<'a, D> {
             .map(|flags| flags.contains(SurfaceType::WINDOW))
     }
 
+    pub fn pbuffer_config(&self) -> QueryResult<bool> {
+        self.surface_type()
+            .map(|flags| flags.contains(SurfaceType::PBUFFER))
+    }
+
+    pub fn pixmap_config(&self) -> QueryResult<bool> {
+        self.surface_type()
+            .map(|flags| flags.contains(SurfaceType::PIXMAP))
+    }
+
     pub fn opengl_config(&self) -> QueryResult<bool> {
         self.client_api()
             .map(|flags| flags.contains(ConfigClientAPI::OPENGL))
@@ -133,6 +151,11 @@ impl<'a, D: DisplayType + 'a> Config<'a, D> {
         self.client_api()
             .map(|flags| flags.contains(ConfigClientAPI::OPENGL_ES3_KHR))
     }
+
+    pub fn openvg_config(&self) -> QueryResult<bool> {
+        self.client_api()
+            .map(|flags| flags.contains(ConfigClientAPI::OPENVG))
+    }
 }
 
 impl<'a, D: DisplayType + 'a> ConfigUtils for Config<'a, D> {
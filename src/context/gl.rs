@@ -1,13 +1,15 @@
 //! OpenGL context
 
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 use egl_sys::ffi::types::EGLint;
 use egl_sys::ffi;
 use egl_sys::extensions;
 
 use platform::Platform;
-use context::{Context, RawContextUtils};
+use display::DisplayHandle;
+use context::{Context, GLContext, RawContextUtils, SharedContextError, SingleContext};
 use config::client_api::ConfigOpenGL;
 use utils::{AttributeListBuilder, PositiveInteger, UnsignedInteger};
 use error::EGLError;
@@ -23,6 +25,10 @@ pub struct OpenGLContext<P: Platform> {
     _marker: PhantomData<ffi::types::EGLContext>,
 }
 
+// A bare `OpenGLContext` is never current: making it current moves it into
+// one of the `!Send` current context types (see `NotSend` in `context`).
+unsafe impl<P: Platform + Send + Sync> Send for OpenGLContext<P> {}
+
 impl<P: Platform> ContextAttributeUtils for OpenGLContext<P> {}
 impl<P: Platform> CommonAttributes for OpenGLContext<P> {}
 
@@ -32,11 +38,15 @@ impl<P: Platform> Drop for OpenGLContext<P> {
     }
 }
 
+impl<P: Platform> GLContext for OpenGLContext<P> {}
+
 impl<P: Platform> RawContextUtils for OpenGLContext<P> {
     const API_TYPE: ffi::types::EGLenum = ffi::OPENGL_API;
 }
 
 impl<P: Platform> Context for OpenGLContext<P> {
+    type Platform = P;
+
     fn raw_display(&self) -> ffi::types::EGLDisplay {
         self.config_opengl.display_config().raw_display()
     }
@@ -48,11 +58,16 @@ impl<P: Platform> Context for OpenGLContext<P> {
     fn egl_handle(&self) -> &EGLHandle {
         self.config_opengl.egl_handle()
     }
+
+    fn display_handle(&self) -> &Arc<DisplayHandle<P>> {
+        self.config_opengl.display_config().display_handle()
+    }
 }
 
 pub struct OpenGLContextBuilder<P: Platform> {
     config_opengl: ConfigOpenGL<P>,
     attributes: AttributeListBuilder,
+    share_context: ffi::types::EGLContext,
 }
 
 impl<P: Platform> OpenGLContextBuilder<P> {
@@ -60,9 +75,15 @@ impl<P: Platform> OpenGLContextBuilder<P> {
         OpenGLContextBuilder {
             config_opengl,
             attributes: AttributeListBuilder::new(),
+            share_context: ffi::NO_CONTEXT,
         }
     }
 
+    /// Default value: `ffi::NO_CONTEXT`
+    pub(crate) fn set_share_context(&mut self, context: &OpenGLContext<P>) {
+        self.share_context = context.raw_context();
+    }
+
     /// This function calls `bind_api` before creating the context.
     pub(crate) fn build(self) -> Result<OpenGLContext<P>, Option<EGLError>> {
         let attribute_list = self.attributes.build();
@@ -75,7 +96,7 @@ impl<P: Platform> OpenGLContextBuilder<P> {
                 CreateContext(
                     self.config_opengl.display_config().raw_display(),
                     self.config_opengl.display_config().raw_config(),
-                    ffi::NO_CONTEXT,
+                    self.share_context,
                     attribute_list.ptr()
                 )
             )
@@ -95,6 +116,52 @@ impl<P: Platform> OpenGLContextBuilder<P> {
     }
 }
 
+impl<P: Platform, D> SingleContext<OpenGLContext<P>, D> {
+    /// Build new context which shares objects like textures and buffers
+    /// with this context.
+    ///
+    /// New context has reference counted handle to the display, so
+    /// display will not be terminated before all contexts of the share
+    /// group are dropped.
+    ///
+    /// Returns `SharedContextError::DifferentDisplay` if the builder's config
+    /// is not from the same display as this context.
+    pub fn build_shared_context(
+        &self,
+        mut builder: OpenGLContextBuilder<P>,
+    ) -> Result<SingleContext<OpenGLContext<P>, Arc<DisplayHandle<P>>>, SharedContextError> {
+        if builder.config_opengl.display_config().raw_display() != self.context().raw_display() {
+            return Err(SharedContextError::DifferentDisplay);
+        }
+
+        builder.set_share_context(self.context());
+
+        let context = builder.build()?;
+        let display_handle = context.display_handle().clone();
+
+        Ok(SingleContext::new(context, display_handle))
+    }
+
+    /// Extension EGL_KHR_create_context
+    ///
+    /// See `build_shared_context` for more information.
+    pub fn build_shared_context_ext(
+        &self,
+        mut builder: OpenGLContextBuilderEXT<P>,
+    ) -> Result<SingleContext<OpenGLContext<P>, Arc<DisplayHandle<P>>>, SharedContextError> {
+        if builder.builder.config_opengl.display_config().raw_display() != self.context().raw_display() {
+            return Err(SharedContextError::DifferentDisplay);
+        }
+
+        builder.set_share_context(self.context());
+
+        let context = builder.build()?;
+        let display_handle = context.display_handle().clone();
+
+        Ok(SingleContext::new(context, display_handle))
+    }
+}
+
 // EGL_KHR_create_context extension implementation
 
 /// OpenGL context builder with EGL_KHR_create_context extension attributes.
@@ -152,6 +219,10 @@ impl<P: Platform> OpenGLContextBuilderEXT<P> {
         );
     }
 
+    pub(crate) fn set_share_context(&mut self, context: &OpenGLContext<P>) {
+        self.builder.set_share_context(context);
+    }
+
     /// This function calls `bind_api` before creating the context.
     pub(crate) fn build(self) -> Result<OpenGLContext<P>, Option<EGLError>> {
         self.builder.build()
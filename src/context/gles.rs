@@ -1,13 +1,15 @@
 //! OpenGL ES context
 
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 use egl_sys::ffi;
 use egl_sys::ffi::types::EGLint;
 use egl_sys::extensions;
 
 use platform::Platform;
-use context::{Context, RawContextUtils};
+use display::DisplayHandle;
+use context::{Context, GLContext, RawContextUtils, SharedContextError, SingleContext};
 use config::client_api::ConfigOpenGLES;
 use utils::{AttributeListBuilder, UnsignedInteger};
 use error::EGLError;
@@ -38,6 +40,10 @@ pub struct OpenGLESContext<P: Platform> {
     _marker: PhantomData<ffi::types::EGLContext>,
 }
 
+// Sound for the same reason as `OpenGLContext`: a current OpenGL ES context
+// is always owned by a `!Send` current context type.
+unsafe impl<P: Platform + Send + Sync> Send for OpenGLESContext<P> {}
+
 impl<P: Platform> ContextAttributeUtils for OpenGLESContext<P> {}
 impl<P: Platform> CommonAttributes for OpenGLESContext<P> {}
 
@@ -49,11 +55,15 @@ impl<P: Platform> Drop for OpenGLESContext<P> {
     }
 }
 
+impl<P: Platform> GLContext for OpenGLESContext<P> {}
+
 impl<P: Platform> RawContextUtils for OpenGLESContext<P> {
     const API_TYPE: ffi::types::EGLenum = ffi::OPENGL_ES_API;
 }
 
 impl<P: Platform> Context for OpenGLESContext<P> {
+    type Platform = P;
+
     fn raw_display(&self) -> ffi::types::EGLDisplay {
         self.config_opengl.display_config().raw_display()
     }
@@ -65,11 +75,16 @@ impl<P: Platform> Context for OpenGLESContext<P> {
     fn egl_handle(&self) -> &EGLHandle {
         self.config_opengl.egl_handle()
     }
+
+    fn display_handle(&self) -> &Arc<DisplayHandle<P>> {
+        self.config_opengl.display_config().display_handle()
+    }
 }
 
 pub struct OpenGLESContextBuilder<P: Platform> {
     config_opengl: ConfigOpenGLES<P>,
     attributes: AttributeListBuilder,
+    share_context: ffi::types::EGLContext,
 }
 
 impl<P: Platform> OpenGLESContextBuilder<P> {
@@ -77,9 +92,15 @@ impl<P: Platform> OpenGLESContextBuilder<P> {
         OpenGLESContextBuilder {
             config_opengl,
             attributes: AttributeListBuilder::new(),
+            share_context: ffi::NO_CONTEXT,
         }
     }
 
+    /// Default value: `ffi::NO_CONTEXT`
+    pub(crate) fn set_share_context(&mut self, context: &OpenGLESContext<P>) {
+        self.share_context = context.raw_context();
+    }
+
     /// Default value: 1
     pub(crate) fn set_context_client_version(&mut self, version: EGL14OpenGLESVersion) {
         self.attributes
@@ -98,7 +119,7 @@ impl<P: Platform> OpenGLESContextBuilder<P> {
                 CreateContext(
                     self.config_opengl.display_config().raw_display(),
                     self.config_opengl.display_config().raw_config(),
-                    ffi::NO_CONTEXT,
+                    self.share_context,
                     attribute_list.ptr()
                 )
             )
@@ -118,6 +139,52 @@ impl<P: Platform> OpenGLESContextBuilder<P> {
     }
 }
 
+impl<P: Platform, D> SingleContext<OpenGLESContext<P>, D> {
+    /// Build new context which shares objects like textures and buffers
+    /// with this context.
+    ///
+    /// New context has reference counted handle to the display, so
+    /// display will not be terminated before all contexts of the share
+    /// group are dropped.
+    ///
+    /// Returns `SharedContextError::DifferentDisplay` if the builder's config
+    /// is not from the same display as this context.
+    pub fn build_shared_context(
+        &self,
+        mut builder: OpenGLESContextBuilder<P>,
+    ) -> Result<SingleContext<OpenGLESContext<P>, Arc<DisplayHandle<P>>>, SharedContextError> {
+        if builder.config_opengl.display_config().raw_display() != self.context().raw_display() {
+            return Err(SharedContextError::DifferentDisplay);
+        }
+
+        builder.set_share_context(self.context());
+
+        let context = builder.build()?;
+        let display_handle = context.display_handle().clone();
+
+        Ok(SingleContext::new(context, display_handle))
+    }
+
+    /// Extension EGL_KHR_create_context
+    ///
+    /// See `build_shared_context` for more information.
+    pub fn build_shared_context_ext(
+        &self,
+        mut builder: OpenGLESContextBuilderEXT<P>,
+    ) -> Result<SingleContext<OpenGLESContext<P>, Arc<DisplayHandle<P>>>, SharedContextError> {
+        if builder.0.config_opengl.display_config().raw_display() != self.context().raw_display() {
+            return Err(SharedContextError::DifferentDisplay);
+        }
+
+        builder.set_share_context(self.context());
+
+        let context = builder.build()?;
+        let display_handle = context.display_handle().clone();
+
+        Ok(SingleContext::new(context, display_handle))
+    }
+}
+
 // EGL_KHR_create_context extension implementation
 
 pub struct OpenGLESContextBuilderEXT<P: Platform>(OpenGLESContextBuilder<P>);
@@ -156,6 +223,10 @@ impl<P: Platform> OpenGLESContextBuilderEXT<P> {
             .add(extensions::CONTEXT_FLAGS_KHR as EGLint, value);
     }
 
+    pub(crate) fn set_share_context(&mut self, context: &OpenGLESContext<P>) {
+        self.0.set_share_context(context);
+    }
+
     /// This function calls `bind_api` before creating the context.
     pub(crate) fn build(self) -> Result<OpenGLESContext<P>, Option<EGLError>> {
         self.0.build()
@@ -3,16 +3,32 @@ pub mod gles;
 pub mod vg;
 pub mod attribute;
 
+use std::marker::PhantomData;
+use std::sync::Arc;
+
 use egl_sys::ffi;
+use egl_sys::ffi::types::{EGLBoolean, EGLint};
+#[cfg(unix)]
+use egl_sys::extensions;
 
 use error::EGLError;
-use surface::Surface;
+use display::{DisplayHandle, DisplayType};
+use platform::Platform;
+use sync::{EGLSync, SyncCreationError, WaitSyncError};
+use surface::{Rect, Surface};
 use surface::attribute::RenderBuffer;
-use utils::{QueryError, QueryResult, UnsignedInteger};
+use utils::{AttributeList, QueryError, QueryResult, UnsignedInteger};
 
 use EGLHandle;
 
-/// Create only one `SingleContext` per Display
+/// Context and the display it was created with.
+///
+/// A `SingleContext` which owns the `Display` is the only context of
+/// that display. Contexts sharing objects with other contexts and
+/// contexts created without consuming the `Display` have reference
+/// counted handle to the display as `D`, so any number of them can
+/// exist. See `build_shared_context` and
+/// `Display::build_opengl_context_with_display_handle` methods.
 #[derive(Debug)]
 pub struct SingleContext<C: Context, D> {
     display: D,
@@ -59,6 +75,7 @@ impl<C: Context, D> SingleContext<C, D> {
             Ok(CurrentSurfaceAndContext {
                 surface,
                 context: self,
+                _not_send: PhantomData,
             })
         } else {
             let error = EGLError::check_errors(self.context.egl_handle());
@@ -76,6 +93,95 @@ impl<C: Context, D> SingleContext<C, D> {
     }
 }
 
+impl<C: Context, D> SingleContext<C, D> {
+    /// Make context current with different draw and read surfaces.
+    ///
+    /// This method call also completes deletion of previously dropped Contexts and Surfaces.
+    pub fn make_current_draw_read<S: Surface, R: Surface>(
+        self,
+        draw_surface: S,
+        read_surface: R,
+    ) -> Result<CurrentDrawReadSurfacesAndContext<S, R, C, D>, ContextOrSurfacesError<S, R, C, D>> {
+        let result = unsafe {
+            egl_function!(
+                self.context.egl_handle(),
+                MakeCurrent(
+                    self.context.raw_display(),
+                    draw_surface.raw_surface(),
+                    read_surface.raw_surface(),
+                    self.context.raw_context()
+                )
+            )
+        };
+
+        if result == ffi::TRUE {
+            Ok(CurrentDrawReadSurfacesAndContext {
+                draw_surface,
+                read_surface,
+                context: self,
+                _not_send: PhantomData,
+            })
+        } else {
+            let error = EGLError::check_errors(self.context.egl_handle());
+
+            match error {
+                Some(EGLError::ContextLost) => Err(ContextOrSurfacesError::ContextLost(
+                    self.display,
+                    draw_surface,
+                    read_surface,
+                )),
+                Some(EGLError::BadNativeWindow) => {
+                    Err(ContextOrSurfacesError::BadNativeWindow(self))
+                }
+                other_error => Err(ContextOrSurfacesError::OtherError(self.display, other_error)),
+            }
+        }
+    }
+}
+
+impl<C: Context, D: DisplayType> SingleContext<C, D> {
+    /// Make context current without default framebuffer.
+    ///
+    /// Extension EGL_KHR_surfaceless_context
+    ///
+    /// The extension is required also with EGL 1.5, because EGL 1.5 did not
+    /// make surfaceless contexts a core feature.
+    ///
+    /// If the extension is not supported, this `SingleContext` is returned back with
+    /// `ContextError::ExtensionNotSupported`.
+    pub fn make_current_surfaceless(self) -> Result<CurrentContext<C, D>, ContextError<C, D>> {
+        if !self.display.display_extensions().surfaceless_context() {
+            return Err(ContextError::ExtensionNotSupported(self));
+        }
+
+        let result = unsafe {
+            egl_function!(
+                self.context.egl_handle(),
+                MakeCurrent(
+                    self.context.raw_display(),
+                    ffi::NO_SURFACE,
+                    ffi::NO_SURFACE,
+                    self.context.raw_context()
+                )
+            )
+        };
+
+        if result == ffi::TRUE {
+            Ok(CurrentContext {
+                context: self,
+                _not_send: PhantomData,
+            })
+        } else {
+            let error = EGLError::check_errors(self.context.egl_handle());
+
+            match error {
+                Some(EGLError::ContextLost) => Err(ContextError::ContextLost(self.display)),
+                other_error => Err(ContextError::OtherError(self.display, other_error)),
+            }
+        }
+    }
+}
+
 pub(crate) trait RawContextUtils: Context {
     const API_TYPE: ffi::types::EGLenum;
 
@@ -91,14 +197,28 @@ pub(crate) trait RawContextUtils: Context {
 }
 
 pub trait Context: Sized {
+    type Platform: Platform;
+
     fn raw_display(&self) -> ffi::types::EGLDisplay;
     fn raw_context(&self) -> ffi::types::EGLContext;
     fn egl_handle(&self) -> &EGLHandle;
+    fn display_handle(&self) -> &Arc<DisplayHandle<Self::Platform>>;
 }
 
+/// OpenGL or OpenGL ES context.
+pub trait GLContext: Context {}
+
+/// Contexts and surfaces implement `Send`, because EGL allows using them
+/// from another thread when they are not current. Types which represent
+/// current context are not `Send`, so a current context or surface can't
+/// be moved to another thread. Release the context first with
+/// `make_not_current`.
+type NotSend = PhantomData<*const ()>;
+
 pub struct CurrentSurfaceAndContext<S: Surface, C: Context, D> {
     surface: S,
     context: SingleContext<C, D>,
+    _not_send: NotSend,
 }
 
 impl<S: Surface, C: Context, D> CurrentSurfaceAndContext<S, C, D> {
@@ -113,6 +233,13 @@ impl<S: Surface, C: Context, D> CurrentSurfaceAndContext<S, C, D> {
             )
         };
 
+        self.swap_buffers_result(result)
+    }
+
+    fn swap_buffers_result(
+        self,
+        result: EGLBoolean,
+    ) -> Result<Self, ContextOrSurfaceError<S, C, D>> {
         if result == ffi::TRUE {
             Ok(self)
         } else {
@@ -149,6 +276,84 @@ impl<S: Surface, C: Context, D> CurrentSurfaceAndContext<S, C, D> {
         }
     }
 
+    /// Release current context and surface.
+    ///
+    /// If there is an error, `self` is returned back.
+    pub fn make_not_current(self) -> Result<(SingleContext<C, D>, S), (Self, Option<EGLError>)> {
+        match release_current_context(self.context.context()) {
+            Ok(()) => Ok((self.context, self.surface)),
+            Err(error) => Err((self, error)),
+        }
+    }
+
+    /// Make server wait until sync object is signaled without blocking
+    /// the calling thread.
+    ///
+    /// Requires EGL 1.5 or extension EGL_KHR_wait_sync.
+    pub fn wait_sync<P: Platform>(&self, sync: &EGLSync<P>) -> Result<(), WaitSyncError> {
+        wait_sync(self.context.context(), sync)
+    }
+
+    /// Create fence sync object. Requires EGL 1.5 or extension EGL_KHR_fence_sync.
+    ///
+    /// Fence command is inserted to the command stream of this context.
+    pub fn create_fence_sync(&self) -> Result<EGLSync<C::Platform>, SyncCreationError> {
+        create_fence_sync(self.context.context())
+    }
+
+    /// Extension EGL_ANDROID_native_fence_sync
+    ///
+    /// Create native fence sync object. Fence command is inserted to the
+    /// command stream of this context.
+    ///
+    /// File descriptor of the fence is available with
+    /// `EGLSync::dup_native_fence_fd` after this context is flushed.
+    #[cfg(unix)]
+    pub fn create_native_fence_sync(&self) -> Result<EGLSync<C::Platform>, SyncCreationError> {
+        create_native_fence_sync(self.context.context())
+    }
+
+    /// Make another surface current while keeping the context current.
+    /// Previous surface is returned with new `CurrentSurfaceAndContext`.
+    ///
+    /// If there is an error, EGL keeps the previous surface current, so
+    /// `self` is returned back with the new surface.
+    pub fn change_surface<T: Surface>(
+        self,
+        surface: T,
+    ) -> Result<(CurrentSurfaceAndContext<T, C, D>, S), (Self, T, Option<EGLError>)> {
+        let result = unsafe {
+            egl_function!(
+                self.context.context().egl_handle(),
+                MakeCurrent(
+                    self.context.context().raw_display(),
+                    surface.raw_surface(),
+                    surface.raw_surface(),
+                    self.context.context().raw_context()
+                )
+            )
+        };
+
+        if result != ffi::TRUE {
+            let error = EGLError::check_errors(self.context.context().egl_handle());
+            return Err((self, surface, error));
+        }
+
+        let CurrentSurfaceAndContext {
+            surface: previous_surface,
+            context,
+            ..
+        } = self;
+
+        let current = CurrentSurfaceAndContext {
+            surface,
+            context,
+            _not_send: PhantomData,
+        };
+
+        Ok((current, previous_surface))
+    }
+
     pub fn context(&self) -> &SingleContext<C, D> {
         &self.context
     }
@@ -166,6 +371,256 @@ impl<S: Surface, C: Context, D> CurrentSurfaceAndContext<S, C, D> {
     }
 }
 
+impl<S: Surface, C: Context, D: DisplayType> CurrentSurfaceAndContext<S, C, D> {
+    /// Swap buffers and tell EGL which regions of the surface have changed.
+    ///
+    /// Uses extension EGL_KHR_swap_buffers_with_damage or
+    /// EGL_EXT_swap_buffers_with_damage. If neither is supported,
+    /// whole surface is swapped with `swap_buffers`.
+    pub fn swap_buffers_with_damage(
+        self,
+        rects: &[Rect],
+    ) -> Result<Self, ContextOrSurfaceError<S, C, D>> {
+        let (khr, ext) = {
+            let extensions = self.context.display.display_extensions();
+            (
+                extensions.swap_buffers_with_damage_khr(),
+                extensions.swap_buffers_with_damage_ext(),
+            )
+        };
+
+        if !khr && !ext {
+            return self.swap_buffers();
+        }
+
+        let mut rect_list = Rect::to_rect_list(rects);
+        let raw_display = self.context.context().raw_display();
+        let raw_surface = self.surface.raw_surface();
+
+        let result = unsafe {
+            let functions = &self.context.context().egl_handle().functions.extensions;
+
+            if khr {
+                functions.SwapBuffersWithDamageKHR(
+                    raw_display,
+                    raw_surface,
+                    rect_list.as_mut_ptr(),
+                    rects.len() as EGLint,
+                )
+            } else {
+                functions.SwapBuffersWithDamageEXT(
+                    raw_display,
+                    raw_surface,
+                    rect_list.as_mut_ptr(),
+                    rects.len() as EGLint,
+                )
+            }
+        };
+
+        self.swap_buffers_result(result)
+    }
+
+    /// Set regions of the surface which will be modified before the
+    /// next buffer swap. Query `buffer_age` before setting the damage region.
+    ///
+    /// Extension EGL_KHR_partial_update. Returns `Ok(false)` if the
+    /// extension is not supported, in which case the whole surface
+    /// can be modified.
+    pub fn set_damage_region(&mut self, rects: &[Rect]) -> Result<bool, Option<EGLError>> {
+        if !self.context.display.display_extensions().partial_update() {
+            return Ok(false);
+        }
+
+        let mut rect_list = Rect::to_rect_list(rects);
+
+        let result = unsafe {
+            self.context
+                .context()
+                .egl_handle()
+                .functions
+                .extensions
+                .SetDamageRegionKHR(
+                    self.context.context().raw_display(),
+                    self.surface.raw_surface(),
+                    rect_list.as_mut_ptr(),
+                    rects.len() as EGLint,
+                )
+        };
+
+        if result == ffi::TRUE {
+            Ok(true)
+        } else {
+            Err(EGLError::check_errors(self.context.context().egl_handle()))
+        }
+    }
+}
+
+/// Current context without default framebuffer.
+///
+/// Extension EGL_KHR_surfaceless_context
+pub struct CurrentContext<C: Context, D> {
+    context: SingleContext<C, D>,
+    _not_send: NotSend,
+}
+
+impl<C: Context, D> CurrentContext<C, D> {
+    /// Release current context.
+    ///
+    /// If there is an error, `self` is returned back.
+    pub fn make_not_current(self) -> Result<SingleContext<C, D>, (Self, Option<EGLError>)> {
+        match release_current_context(self.context.context()) {
+            Ok(()) => Ok(self.context),
+            Err(error) => Err((self, error)),
+        }
+    }
+
+    /// Make server wait until sync object is signaled without blocking
+    /// the calling thread.
+    ///
+    /// Requires EGL 1.5 or extension EGL_KHR_wait_sync.
+    pub fn wait_sync<P: Platform>(&self, sync: &EGLSync<P>) -> Result<(), WaitSyncError> {
+        wait_sync(self.context.context(), sync)
+    }
+
+    /// Create fence sync object. Requires EGL 1.5 or extension EGL_KHR_fence_sync.
+    ///
+    /// Fence command is inserted to the command stream of this context.
+    pub fn create_fence_sync(&self) -> Result<EGLSync<C::Platform>, SyncCreationError> {
+        create_fence_sync(self.context.context())
+    }
+
+    /// Extension EGL_ANDROID_native_fence_sync
+    ///
+    /// Create native fence sync object. Fence command is inserted to the
+    /// command stream of this context.
+    ///
+    /// File descriptor of the fence is available with
+    /// `EGLSync::dup_native_fence_fd` after this context is flushed.
+    #[cfg(unix)]
+    pub fn create_native_fence_sync(&self) -> Result<EGLSync<C::Platform>, SyncCreationError> {
+        create_native_fence_sync(self.context.context())
+    }
+
+    pub fn context(&self) -> &SingleContext<C, D> {
+        &self.context
+    }
+}
+
+/// Current context with different draw and read surfaces.
+pub struct CurrentDrawReadSurfacesAndContext<S: Surface, R: Surface, C: Context, D> {
+    draw_surface: S,
+    read_surface: R,
+    context: SingleContext<C, D>,
+    _not_send: NotSend,
+}
+
+impl<S: Surface, R: Surface, C: Context, D> CurrentDrawReadSurfacesAndContext<S, R, C, D> {
+    /// Swap buffers of the draw surface.
+    pub fn swap_buffers(self) -> Result<Self, ContextOrSurfacesError<S, R, C, D>> {
+        let result = unsafe {
+            egl_function!(
+                self.context.context().egl_handle(),
+                SwapBuffers(
+                    self.context.context().raw_display(),
+                    self.draw_surface.raw_surface()
+                )
+            )
+        };
+
+        if result == ffi::TRUE {
+            Ok(self)
+        } else {
+            let error = EGLError::check_errors(self.context.context().egl_handle());
+
+            match error {
+                Some(EGLError::ContextLost) => Err(ContextOrSurfacesError::ContextLost(
+                    self.context.display,
+                    self.draw_surface,
+                    self.read_surface,
+                )),
+                Some(EGLError::BadNativeWindow) => {
+                    Err(ContextOrSurfacesError::BadNativeWindow(self.context))
+                }
+                other_error => Err(ContextOrSurfacesError::OtherError(
+                    self.context.display,
+                    other_error,
+                )),
+            }
+        }
+    }
+
+    /// Release current context and surfaces.
+    ///
+    /// If there is an error, `self` is returned back.
+    pub fn make_not_current(self) -> Result<(SingleContext<C, D>, S, R), (Self, Option<EGLError>)> {
+        match release_current_context(self.context.context()) {
+            Ok(()) => Ok((self.context, self.draw_surface, self.read_surface)),
+            Err(error) => Err((self, error)),
+        }
+    }
+
+    /// Make server wait until sync object is signaled without blocking
+    /// the calling thread.
+    ///
+    /// Requires EGL 1.5 or extension EGL_KHR_wait_sync.
+    pub fn wait_sync<P: Platform>(&self, sync: &EGLSync<P>) -> Result<(), WaitSyncError> {
+        wait_sync(self.context.context(), sync)
+    }
+
+    /// Create fence sync object. Requires EGL 1.5 or extension EGL_KHR_fence_sync.
+    ///
+    /// Fence command is inserted to the command stream of this context.
+    pub fn create_fence_sync(&self) -> Result<EGLSync<C::Platform>, SyncCreationError> {
+        create_fence_sync(self.context.context())
+    }
+
+    /// Extension EGL_ANDROID_native_fence_sync
+    ///
+    /// Create native fence sync object. Fence command is inserted to the
+    /// command stream of this context.
+    ///
+    /// File descriptor of the fence is available with
+    /// `EGLSync::dup_native_fence_fd` after this context is flushed.
+    #[cfg(unix)]
+    pub fn create_native_fence_sync(&self) -> Result<EGLSync<C::Platform>, SyncCreationError> {
+        create_native_fence_sync(self.context.context())
+    }
+
+    /// Check with `eglGetCurrentSurface(EGL_DRAW)` that the draw surface is current.
+    pub fn draw_surface_is_current(&self) -> bool {
+        let raw_surface = current_surface(self.context.context().egl_handle(), ffi::DRAW);
+
+        raw_surface == self.draw_surface.raw_surface()
+    }
+
+    /// Check with `eglGetCurrentSurface(EGL_READ)` that the read surface is current.
+    pub fn read_surface_is_current(&self) -> bool {
+        let raw_surface = current_surface(self.context.context().egl_handle(), ffi::READ);
+
+        raw_surface == self.read_surface.raw_surface()
+    }
+
+    pub fn context(&self) -> &SingleContext<C, D> {
+        &self.context
+    }
+
+    pub fn draw_surface(&self) -> &S {
+        &self.draw_surface
+    }
+
+    pub fn draw_surface_mut(&mut self) -> &mut S {
+        &mut self.draw_surface
+    }
+
+    pub fn read_surface(&self) -> &R {
+        &self.read_surface
+    }
+
+    pub fn read_surface_mut(&mut self) -> &mut R {
+        &mut self.read_surface
+    }
+}
+
 impl<S: Surface, C: Context + attribute::ContextAttributeUtils, D>
     CurrentSurfaceAndContext<S, C, D> {
     pub fn render_buffer(&self) -> QueryResult<RenderBuffer> {
@@ -181,6 +636,69 @@ impl<S: Surface, C: Context + attribute::ContextAttributeUtils, D>
     }
 }
 
+pub(self) fn release_current_context<C: Context>(context: &C) -> Result<(), Option<EGLError>> {
+    let result = unsafe {
+        egl_function!(
+            context.egl_handle(),
+            MakeCurrent(
+                context.raw_display(),
+                ffi::NO_SURFACE,
+                ffi::NO_SURFACE,
+                ffi::NO_CONTEXT
+            )
+        )
+    };
+
+    if result == ffi::TRUE {
+        Ok(())
+    } else {
+        Err(EGLError::check_errors(context.egl_handle()))
+    }
+}
+
+pub(self) fn wait_sync<C: Context, P: Platform>(
+    context: &C,
+    sync: &EGLSync<P>,
+) -> Result<(), WaitSyncError> {
+    if context.raw_display() != sync.raw_display() {
+        return Err(WaitSyncError::DifferentDisplay);
+    }
+
+    sync.server_wait()
+}
+
+pub(self) fn create_fence_sync<C: Context>(
+    context: &C,
+) -> Result<EGLSync<C::Platform>, SyncCreationError> {
+    EGLSync::new(
+        context.display_handle(),
+        ffi::SYNC_FENCE,
+        AttributeList::empty(),
+    )
+}
+
+#[cfg(unix)]
+pub(self) fn create_native_fence_sync<C: Context>(
+    context: &C,
+) -> Result<EGLSync<C::Platform>, SyncCreationError> {
+    if !context.display_handle().display_extensions().android_native_fence_sync() {
+        return Err(SyncCreationError::NotSupported);
+    }
+
+    EGLSync::new(
+        context.display_handle(),
+        extensions::SYNC_NATIVE_FENCE_ANDROID,
+        AttributeList::empty(),
+    )
+}
+
+pub(self) fn current_surface(
+    _egl_handle: &EGLHandle,
+    read_or_draw: ffi::types::EGLenum,
+) -> ffi::types::EGLSurface {
+    unsafe { egl_function!(_egl_handle, GetCurrentSurface(read_or_draw as EGLint)) }
+}
+
 pub(self) fn destroy_context(
     _egl_handle: &EGLHandle,
     raw_display: ffi::types::EGLDisplay,
@@ -200,4 +718,30 @@ pub enum ContextOrSurfaceError<S: Surface, C: Context, D> {
     OtherError(D, Option<EGLError>),
 }
 
-// TODO: extension KHR_create_context current contexts without default framebuffer
+#[derive(Debug)]
+pub enum ContextOrSurfacesError<S: Surface, R: Surface, C: Context, D> {
+    ContextLost(D, S, R),
+    BadNativeWindow(SingleContext<C, D>),
+    OtherError(D, Option<EGLError>),
+}
+
+#[derive(Debug)]
+pub enum ContextError<C: Context, D> {
+    ExtensionNotSupported(SingleContext<C, D>),
+    ContextLost(D),
+    OtherError(D, Option<EGLError>),
+}
+
+#[derive(Debug)]
+pub enum SharedContextError {
+    /// Config of the context builder is not from the same display as
+    /// the shared context.
+    DifferentDisplay,
+    EGLError(Option<EGLError>),
+}
+
+impl From<Option<EGLError>> for SharedContextError {
+    fn from(error: Option<EGLError>) -> Self {
+        SharedContextError::EGLError(error)
+    }
+}
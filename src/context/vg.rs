@@ -1 +1,105 @@
-// TODO: OpenVG context
+//! OpenVG context
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use egl_sys::ffi;
+
+use platform::Platform;
+use display::DisplayHandle;
+use context::{Context, RawContextUtils};
+use config::client_api::ConfigOpenVG;
+use utils::AttributeListBuilder;
+use error::EGLError;
+use EGLHandle;
+
+use super::attribute::{CommonAttributes, ContextAttributeUtils};
+
+#[derive(Debug)]
+pub struct OpenVGContext<P: Platform> {
+    config_openvg: ConfigOpenVG<P>,
+    raw_context: ffi::types::EGLContext,
+    _marker: PhantomData<ffi::types::EGLContext>,
+}
+
+// Sound for the same reason as `OpenGLContext`: a current OpenVG context
+// is always owned by a `!Send` current context type.
+unsafe impl<P: Platform + Send + Sync> Send for OpenVGContext<P> {}
+
+impl<P: Platform> ContextAttributeUtils for OpenVGContext<P> {}
+impl<P: Platform> CommonAttributes for OpenVGContext<P> {}
+
+impl<P: Platform> Drop for OpenVGContext<P> {
+    fn drop(&mut self) {
+        super::destroy_context(self.config_openvg.egl_handle(), self.raw_display(), self.raw_context);
+    }
+}
+
+impl<P: Platform> RawContextUtils for OpenVGContext<P> {
+    const API_TYPE: ffi::types::EGLenum = ffi::OPENVG_API;
+}
+
+impl<P: Platform> Context for OpenVGContext<P> {
+    type Platform = P;
+
+    fn raw_display(&self) -> ffi::types::EGLDisplay {
+        self.config_openvg.display_config().raw_display()
+    }
+
+    fn raw_context(&self) -> ffi::types::EGLContext {
+        self.raw_context
+    }
+
+    fn egl_handle(&self) -> &EGLHandle {
+        self.config_openvg.egl_handle()
+    }
+
+    fn display_handle(&self) -> &Arc<DisplayHandle<P>> {
+        self.config_openvg.display_config().display_handle()
+    }
+}
+
+pub struct OpenVGContextBuilder<P: Platform> {
+    config_openvg: ConfigOpenVG<P>,
+    attributes: AttributeListBuilder,
+}
+
+impl<P: Platform> OpenVGContextBuilder<P> {
+    pub(crate) fn new(config_openvg: ConfigOpenVG<P>) -> Self {
+        OpenVGContextBuilder {
+            config_openvg,
+            attributes: AttributeListBuilder::new(),
+        }
+    }
+
+    /// This function calls `bind_api` before creating the context.
+    pub(crate) fn build(self) -> Result<OpenVGContext<P>, Option<EGLError>> {
+        let attribute_list = self.attributes.build();
+
+        OpenVGContext::<P>::bind_api(self.config_openvg.egl_handle())?;
+
+        let raw_context = unsafe {
+            egl_function!(
+                self.config_openvg.egl_handle(),
+                CreateContext(
+                    self.config_openvg.display_config().raw_display(),
+                    self.config_openvg.display_config().raw_config(),
+                    ffi::NO_CONTEXT,
+                    attribute_list.ptr()
+                )
+            )
+        };
+
+        if raw_context == ffi::NO_CONTEXT {
+            Err(EGLError::check_errors(self.config_openvg.egl_handle()))
+        } else {
+            let context = OpenVGContext {
+                config_openvg: self.config_openvg,
+                raw_context,
+                _marker: PhantomData,
+            };
+
+            Ok(context)
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! EGL extensions EGL_EXT_device_enumeration, EGL_EXT_device_query
+//! and EGL_EXT_device_drm.
+
+use std::borrow::Cow;
+use std::ffi::CStr;
+use std::ptr;
+
+use egl_sys::ffi;
+use egl_sys::ffi::types::{EGLDeviceEXT, EGLint};
+use egl_sys::extensions;
+
+use error::EGLError;
+use EGLHandle;
+
+#[derive(Debug)]
+pub enum DeviceQueryError {
+    ExtensionNotSupported,
+    EGLError(Option<EGLError>),
+}
+
+#[derive(Debug, Clone)]
+pub struct DeviceExtensionSupport {
+    ext_device_drm: bool,
+    ext_device_drm_render_node: bool,
+}
+
+impl DeviceExtensionSupport {
+    fn new() -> DeviceExtensionSupport {
+        DeviceExtensionSupport {
+            ext_device_drm: false,
+            ext_device_drm_render_node: false,
+        }
+    }
+
+    fn parse(extensions: &str) -> DeviceExtensionSupport {
+        let mut extension_support = DeviceExtensionSupport::new();
+
+        for ext in extensions.split_whitespace() {
+            match ext {
+                "EGL_EXT_device_drm" => extension_support.ext_device_drm = true,
+                "EGL_EXT_device_drm_render_node" => {
+                    extension_support.ext_device_drm_render_node = true
+                }
+                _ => (),
+            }
+        }
+
+        extension_support
+    }
+
+    pub fn ext_device_drm(&self) -> bool {
+        self.ext_device_drm
+    }
+
+    pub fn ext_device_drm_render_node(&self) -> bool {
+        self.ext_device_drm_render_node
+    }
+}
+
+/// Handle to `EGLDeviceEXT`.
+///
+/// Devices are owned by the EGL implementation, so there is nothing to destroy.
+#[derive(Debug, Clone)]
+pub struct EGLDevice {
+    raw_device: EGLDeviceEXT,
+    extension_support: DeviceExtensionSupport,
+    egl_handle: EGLHandle,
+}
+
+impl EGLDevice {
+    /// Caller must check that extensions EGL_EXT_device_enumeration and
+    /// EGL_EXT_device_query are supported.
+    pub(crate) fn query_devices(egl_handle: &EGLHandle) -> Result<Vec<EGLDevice>, DeviceQueryError> {
+        let functions = &egl_handle.functions.extensions;
+
+        let mut count = 0;
+
+        let result = unsafe { functions.QueryDevicesEXT(0, ptr::null_mut(), &mut count) };
+
+        if result == ffi::FALSE {
+            return Err(DeviceQueryError::EGLError(EGLError::check_errors(egl_handle)));
+        }
+
+        if count <= 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut raw_devices: Vec<EGLDeviceEXT> = Vec::with_capacity(count as usize);
+        let mut new_count = 0;
+
+        unsafe {
+            let result = functions.QueryDevicesEXT(
+                count,
+                raw_devices.as_mut_slice().as_mut_ptr(),
+                &mut new_count,
+            );
+
+            if result == ffi::FALSE {
+                return Err(DeviceQueryError::EGLError(EGLError::check_errors(egl_handle)));
+            }
+
+            if new_count < 0 || count < new_count {
+                return Err(DeviceQueryError::EGLError(None));
+            }
+
+            raw_devices.set_len(new_count as usize);
+        }
+
+        let devices = raw_devices
+            .into_iter()
+            .map(|raw_device| {
+                let mut device = EGLDevice {
+                    raw_device,
+                    extension_support: DeviceExtensionSupport::new(),
+                    egl_handle: egl_handle.clone(),
+                };
+
+                let parsed_extensions = match device.extensions() {
+                    Ok(text) => Some(DeviceExtensionSupport::parse(&text)),
+                    Err(()) => None,
+                };
+
+                if let Some(ext) = parsed_extensions {
+                    device.extension_support = ext;
+                }
+
+                device
+            })
+            .collect();
+
+        Ok(devices)
+    }
+
+    pub fn raw_device(&self) -> EGLDeviceEXT {
+        self.raw_device
+    }
+
+    pub fn extensions(&self) -> Result<Cow<str>, ()> {
+        self.query_string(ffi::EXTENSIONS as EGLint)
+    }
+
+    pub fn device_extensions(&self) -> &DeviceExtensionSupport {
+        &self.extension_support
+    }
+
+    /// Extension EGL_EXT_device_drm
+    ///
+    /// Returns `Ok(None)` if extension is not supported by the device.
+    pub fn drm_device_file(&self) -> Result<Option<Cow<str>>, ()> {
+        if !self.extension_support.ext_device_drm() {
+            return Ok(None);
+        }
+
+        self.query_string(extensions::DRM_DEVICE_FILE_EXT as EGLint)
+            .map(Some)
+    }
+
+    /// Extension EGL_EXT_device_drm_render_node
+    ///
+    /// Returns `Ok(None)` if extension is not supported by the device.
+    pub fn drm_render_node_file(&self) -> Result<Option<Cow<str>>, ()> {
+        if !self.extension_support.ext_device_drm_render_node() {
+            return Ok(None);
+        }
+
+        self.query_string(extensions::DRM_RENDER_NODE_FILE_EXT as EGLint)
+            .map(Some)
+    }
+
+    fn query_string(&self, name: EGLint) -> Result<Cow<str>, ()> {
+        unsafe {
+            let ptr = self.egl_handle
+                .functions
+                .extensions
+                .QueryDeviceStringEXT(self.raw_device, name);
+
+            if ptr.is_null() {
+                return Err(());
+            }
+
+            let cstr = CStr::from_ptr(ptr);
+
+            Ok(cstr.to_string_lossy())
+        }
+    }
+}
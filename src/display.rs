@@ -3,7 +3,6 @@ use std::ptr;
 use std::borrow::Cow;
 use std::marker::PhantomData;
 use std::sync::Arc;
-use std::mem;
 use std::os;
 
 use egl_sys::ffi;
@@ -13,9 +12,21 @@ use config::Configs;
 use config::search::{ConfigSearchOptions, ConfigSearchOptionsBuilder};
 use context::gl::{OpenGLContext, OpenGLContextBuilder, OpenGLContextBuilderEXT};
 use context::gles::{OpenGLESContext, OpenGLESContextBuilder, OpenGLESContextBuilderEXT};
-use context::SingleContext;
+use context::vg::{OpenVGContext, OpenVGContextBuilder};
+use context::{Context, GLContext, SingleContext};
 use error::EGLError;
 use platform::Platform;
+use sync::{EGLSync, SyncCreationError};
+use image::{EGLImage, GLImageSource, ImageAttributeList, ImageCreationError};
+
+#[cfg(unix)]
+use std::os::unix::io::{AsRawFd, IntoRawFd};
+#[cfg(unix)]
+use egl_sys::extensions;
+#[cfg(unix)]
+use utils::{AttributeListBuilder, OwnedFd};
+#[cfg(unix)]
+use image::dma_buf::{self, DmaBufImageBuilder, DmaBufModifier, DmaBufQueryError};
 
 use EGLHandle;
 
@@ -23,6 +34,32 @@ use EGLHandle;
 pub struct DisplayExtensionSupport {
     get_all_proc_addresses: bool,
     create_context: bool,
+    surfaceless_context: bool,
+    fence_sync: bool,
+    wait_sync: bool,
+    android_native_fence_sync: bool,
+    image_base: bool,
+    gl_texture_2d_image: bool,
+    gl_texture_cubemap_image: bool,
+    gl_texture_3d_image: bool,
+    gl_renderbuffer_image: bool,
+    image_dma_buf_import: bool,
+    image_dma_buf_import_modifiers: bool,
+    mesa_image_dma_buf_export: bool,
+    gl_colorspace: bool,
+    gl_colorspace_bt2020_linear: bool,
+    gl_colorspace_bt2020_pq: bool,
+    gl_colorspace_bt2020_hlg: bool,
+    gl_colorspace_scrgb: bool,
+    gl_colorspace_scrgb_linear: bool,
+    gl_colorspace_display_p3: bool,
+    gl_colorspace_display_p3_linear: bool,
+    surface_smpte2086_metadata: bool,
+    surface_cta861_3_metadata: bool,
+    swap_buffers_with_damage_khr: bool,
+    swap_buffers_with_damage_ext: bool,
+    buffer_age: bool,
+    partial_update: bool,
 }
 
 impl DisplayExtensionSupport {
@@ -30,26 +67,265 @@ impl DisplayExtensionSupport {
         DisplayExtensionSupport {
             get_all_proc_addresses: false,
             create_context: false,
+            surfaceless_context: false,
+            fence_sync: false,
+            wait_sync: false,
+            android_native_fence_sync: false,
+            image_base: false,
+            gl_texture_2d_image: false,
+            gl_texture_cubemap_image: false,
+            gl_texture_3d_image: false,
+            gl_renderbuffer_image: false,
+            image_dma_buf_import: false,
+            image_dma_buf_import_modifiers: false,
+            mesa_image_dma_buf_export: false,
+            gl_colorspace: false,
+            gl_colorspace_bt2020_linear: false,
+            gl_colorspace_bt2020_pq: false,
+            gl_colorspace_bt2020_hlg: false,
+            gl_colorspace_scrgb: false,
+            gl_colorspace_scrgb_linear: false,
+            gl_colorspace_display_p3: false,
+            gl_colorspace_display_p3_linear: false,
+            surface_smpte2086_metadata: false,
+            surface_cta861_3_metadata: false,
+            swap_buffers_with_damage_khr: false,
+            swap_buffers_with_damage_ext: false,
+            buffer_age: false,
+            partial_update: false,
         }
     }
 
-    fn parse(extensions: &str) -> DisplayExtensionSupport {
+    fn parse(extensions: &str, egl_handle: &EGLHandle) -> DisplayExtensionSupport {
         let mut extension_support = DisplayExtensionSupport::new();
 
         for ext in extensions.split_whitespace() {
             match ext {
                 "EGL_KHR_get_all_proc_addresses" => extension_support.get_all_proc_addresses = true,
                 "EGL_KHR_create_context" => extension_support.create_context = true,
+                "EGL_KHR_surfaceless_context" => extension_support.surfaceless_context = true,
+                "EGL_KHR_fence_sync" => extension_support.fence_sync = true,
+                "EGL_KHR_wait_sync" => extension_support.wait_sync = true,
+                "EGL_ANDROID_native_fence_sync" => {
+                    extension_support.android_native_fence_sync = true
+                }
+                "EGL_KHR_image_base" => extension_support.image_base = true,
+                "EGL_KHR_gl_texture_2D_image" => extension_support.gl_texture_2d_image = true,
+                "EGL_KHR_gl_texture_cubemap_image" => {
+                    extension_support.gl_texture_cubemap_image = true
+                }
+                "EGL_KHR_gl_texture_3D_image" => extension_support.gl_texture_3d_image = true,
+                "EGL_KHR_gl_renderbuffer_image" => {
+                    extension_support.gl_renderbuffer_image = true
+                }
+                "EGL_EXT_image_dma_buf_import" => extension_support.image_dma_buf_import = true,
+                "EGL_EXT_image_dma_buf_import_modifiers" => {
+                    extension_support.image_dma_buf_import_modifiers = true
+                }
+                "EGL_MESA_image_dma_buf_export" => {
+                    extension_support.mesa_image_dma_buf_export = true
+                }
+                "EGL_KHR_gl_colorspace" => extension_support.gl_colorspace = true,
+                "EGL_EXT_gl_colorspace_bt2020_linear" => {
+                    extension_support.gl_colorspace_bt2020_linear = true
+                }
+                "EGL_EXT_gl_colorspace_bt2020_pq" => {
+                    extension_support.gl_colorspace_bt2020_pq = true
+                }
+                "EGL_EXT_gl_colorspace_bt2020_hlg" => {
+                    extension_support.gl_colorspace_bt2020_hlg = true
+                }
+                "EGL_EXT_gl_colorspace_scrgb" => extension_support.gl_colorspace_scrgb = true,
+                "EGL_EXT_gl_colorspace_scrgb_linear" => {
+                    extension_support.gl_colorspace_scrgb_linear = true
+                }
+                "EGL_EXT_gl_colorspace_display_p3" => {
+                    extension_support.gl_colorspace_display_p3 = true
+                }
+                "EGL_EXT_gl_colorspace_display_p3_linear" => {
+                    extension_support.gl_colorspace_display_p3_linear = true
+                }
+                "EGL_EXT_surface_SMPTE2086_metadata" => {
+                    extension_support.surface_smpte2086_metadata = true
+                }
+                "EGL_EXT_surface_CTA861_3_metadata" => {
+                    extension_support.surface_cta861_3_metadata = true
+                }
+                "EGL_KHR_swap_buffers_with_damage" => {
+                    extension_support.swap_buffers_with_damage_khr = true
+                }
+                "EGL_EXT_swap_buffers_with_damage" => {
+                    extension_support.swap_buffers_with_damage_ext = true
+                }
+                "EGL_EXT_buffer_age" => extension_support.buffer_age = true,
+                "EGL_KHR_partial_update" => extension_support.partial_update = true,
                 _ => (),
             }
         }
 
+        let functions = &egl_handle.functions.extensions;
+
+        if extension_support.fence_sync {
+            extension_support.fence_sync = functions.CreateSyncKHR.is_loaded() &&
+                functions.DestroySyncKHR.is_loaded() &&
+                functions.ClientWaitSyncKHR.is_loaded() &&
+                functions.GetSyncAttribKHR.is_loaded();
+        }
+
+        // EGL_KHR_wait_sync requires EGL_KHR_fence_sync.
+        if extension_support.wait_sync {
+            extension_support.wait_sync = extension_support.fence_sync &&
+                functions.WaitSyncKHR.is_loaded();
+        }
+
+        if extension_support.android_native_fence_sync {
+            extension_support.android_native_fence_sync =
+                functions.DupNativeFenceFDANDROID.is_loaded();
+        }
+
+        if extension_support.image_base {
+            extension_support.image_base = functions.CreateImageKHR.is_loaded() &&
+                functions.DestroyImageKHR.is_loaded();
+        }
+
+        // EGL_EXT_image_dma_buf_import_modifiers requires EGL_EXT_image_dma_buf_import.
+        if extension_support.image_dma_buf_import_modifiers {
+            extension_support.image_dma_buf_import_modifiers =
+                extension_support.image_dma_buf_import &&
+                    functions.QueryDmaBufFormatsEXT.is_loaded() &&
+                    functions.QueryDmaBufModifiersEXT.is_loaded();
+        }
+
+        if extension_support.mesa_image_dma_buf_export {
+            extension_support.mesa_image_dma_buf_export =
+                functions.ExportDMABUFImageQueryMESA.is_loaded() &&
+                    functions.ExportDMABUFImageMESA.is_loaded();
+        }
+
+        if extension_support.swap_buffers_with_damage_khr {
+            extension_support.swap_buffers_with_damage_khr =
+                functions.SwapBuffersWithDamageKHR.is_loaded();
+        }
+
+        if extension_support.swap_buffers_with_damage_ext {
+            extension_support.swap_buffers_with_damage_ext =
+                functions.SwapBuffersWithDamageEXT.is_loaded();
+        }
+
+        if extension_support.partial_update {
+            extension_support.partial_update = functions.SetDamageRegionKHR.is_loaded();
+        }
+
         extension_support
     }
 
     pub fn create_context(&self) -> bool {
         self.create_context
     }
+
+    pub fn surfaceless_context(&self) -> bool {
+        self.surfaceless_context
+    }
+
+    pub fn fence_sync(&self) -> bool {
+        self.fence_sync
+    }
+
+    pub fn wait_sync(&self) -> bool {
+        self.wait_sync
+    }
+
+    pub fn android_native_fence_sync(&self) -> bool {
+        self.android_native_fence_sync
+    }
+
+    pub fn image_base(&self) -> bool {
+        self.image_base
+    }
+
+    pub fn gl_texture_2d_image(&self) -> bool {
+        self.gl_texture_2d_image
+    }
+
+    pub fn gl_texture_cubemap_image(&self) -> bool {
+        self.gl_texture_cubemap_image
+    }
+
+    pub fn gl_texture_3d_image(&self) -> bool {
+        self.gl_texture_3d_image
+    }
+
+    pub fn gl_renderbuffer_image(&self) -> bool {
+        self.gl_renderbuffer_image
+    }
+
+    pub fn image_dma_buf_import(&self) -> bool {
+        self.image_dma_buf_import
+    }
+
+    pub fn image_dma_buf_import_modifiers(&self) -> bool {
+        self.image_dma_buf_import_modifiers
+    }
+
+    pub fn mesa_image_dma_buf_export(&self) -> bool {
+        self.mesa_image_dma_buf_export
+    }
+
+    pub fn gl_colorspace(&self) -> bool {
+        self.gl_colorspace
+    }
+
+    pub fn gl_colorspace_bt2020_linear(&self) -> bool {
+        self.gl_colorspace_bt2020_linear
+    }
+
+    pub fn gl_colorspace_bt2020_pq(&self) -> bool {
+        self.gl_colorspace_bt2020_pq
+    }
+
+    pub fn gl_colorspace_bt2020_hlg(&self) -> bool {
+        self.gl_colorspace_bt2020_hlg
+    }
+
+    pub fn gl_colorspace_scrgb(&self) -> bool {
+        self.gl_colorspace_scrgb
+    }
+
+    pub fn gl_colorspace_scrgb_linear(&self) -> bool {
+        self.gl_colorspace_scrgb_linear
+    }
+
+    pub fn gl_colorspace_display_p3(&self) -> bool {
+        self.gl_colorspace_display_p3
+    }
+
+    pub fn gl_colorspace_display_p3_linear(&self) -> bool {
+        self.gl_colorspace_display_p3_linear
+    }
+
+    pub fn surface_smpte2086_metadata(&self) -> bool {
+        self.surface_smpte2086_metadata
+    }
+
+    pub fn surface_cta861_3_metadata(&self) -> bool {
+        self.surface_cta861_3_metadata
+    }
+
+    pub fn swap_buffers_with_damage_khr(&self) -> bool {
+        self.swap_buffers_with_damage_khr
+    }
+
+    pub fn swap_buffers_with_damage_ext(&self) -> bool {
+        self.swap_buffers_with_damage_ext
+    }
+
+    pub fn buffer_age(&self) -> bool {
+        self.buffer_age
+    }
+
+    pub fn partial_update(&self) -> bool {
+        self.partial_update
+    }
 }
 
 #[derive(Debug)]
@@ -111,18 +387,33 @@ impl EGLVersion {
     }
 }
 
+/// Reference counted EGLDisplay. Display is terminated when the last
+/// handle is dropped.
+///
+/// Dropping does not call `eglReleaseThread`. The last handle can be
+/// dropped on any thread, and releasing that thread would also release
+/// its current context, which may belong to another display.
 #[derive(Debug)]
 pub struct DisplayHandle<P: Platform> {
     platform: P,
     raw_display: ffi::types::EGLDisplay,
+    egl_version: EGLVersion,
+    extension_support: DisplayExtensionSupport,
     _marker: PhantomData<ffi::types::EGLDisplay>,
 }
 
 impl<P: Platform> DisplayHandle<P> {
-    fn new_in_arc(raw_display: ffi::types::EGLDisplay, platform: P) -> Arc<Self> {
+    fn new_in_arc(
+        raw_display: ffi::types::EGLDisplay,
+        platform: P,
+        egl_version: EGLVersion,
+        extension_support: DisplayExtensionSupport,
+    ) -> Arc<Self> {
         let display_handle = DisplayHandle {
             platform,
             raw_display,
+            egl_version,
+            extension_support,
             _marker: PhantomData,
         };
 
@@ -147,6 +438,10 @@ impl<P: Platform> DisplayHandle<P> {
     }
 }
 
+// EGL functions are thread safe, so EGLDisplay can be used from multiple threads.
+unsafe impl<P: Platform + Send> Send for DisplayHandle<P> {}
+unsafe impl<P: Platform + Sync> Sync for DisplayHandle<P> {}
+
 impl<P: Platform> Drop for DisplayHandle<P> {
     fn drop(&mut self) {
         let result = unsafe { egl_function!(self.egl_handle(), Terminate(self.raw_display)) };
@@ -154,12 +449,6 @@ impl<P: Platform> Drop for DisplayHandle<P> {
         if result == ffi::FALSE {
             eprintln!("egl_wrapper: eglTerminate returned false");
         }
-
-        let result = unsafe { egl_function!(self.egl_handle(), ReleaseThread()) };
-
-        if result == ffi::FALSE {
-            eprintln!("egl_wrapper: eglReleaseThread returned false");
-        }
     }
 }
 
@@ -168,8 +457,6 @@ impl<P: Platform> Drop for DisplayHandle<P> {
 /// EGLDisplay with initialized EGL
 #[derive(Debug)]
 pub struct Display<P: Platform> {
-    extension_support: DisplayExtensionSupport,
-    egl_version: EGLVersion,
     display_handle: Arc<DisplayHandle<P>>,
 }
 
@@ -189,25 +476,24 @@ impl<P: Platform> Display<P> {
         }
 
         let version = EGLVersion::parse(version_major, version_minor);
-        let extension_support = DisplayExtensionSupport::new();
 
         match version {
             Some(version) => {
-                let mut display = Display {
-                    extension_support,
-                    egl_version: version,
-                    display_handle: DisplayHandle::new_in_arc(raw_display, platform),
-                };
+                let extension_support =
+                    match query_string(platform.egl_handle(), raw_display, ffi::EXTENSIONS as EGLint) {
+                        Ok(text) => DisplayExtensionSupport::parse(&text, platform.egl_handle()),
+                        Err(()) => DisplayExtensionSupport::new(),
+                    };
 
-                let parsed_extensions = match display.extensions() {
-                    Ok(text) => Some(DisplayExtensionSupport::parse(&text)),
-                    Err(()) => None,
+                let display = Display {
+                    display_handle: DisplayHandle::new_in_arc(
+                        raw_display,
+                        platform,
+                        version,
+                        extension_support,
+                    ),
                 };
 
-                if let Some(ext) = parsed_extensions {
-                    mem::replace(&mut display.extension_support, ext);
-                }
-
                 Ok(display)
             }
             None => {
@@ -215,9 +501,12 @@ impl<P: Platform> Display<P> {
                 // return error.
 
                 let display = Display {
-                    extension_support,
-                    egl_version: EGLVersion::EGL_1_4,
-                    display_handle: DisplayHandle::new_in_arc(raw_display, platform),
+                    display_handle: DisplayHandle::new_in_arc(
+                        raw_display,
+                        platform,
+                        EGLVersion::EGL_1_4,
+                        DisplayExtensionSupport::new(),
+                    ),
                 };
 
                 drop(display);
@@ -244,17 +533,7 @@ impl<P: Platform> Display<P> {
     }
 
     fn query_string(&self, name: EGLint) -> Result<Cow<str>, ()> {
-        unsafe {
-            let ptr = egl_function!(self.display_handle().egl_handle(), QueryString(self.display_handle().raw_display(), name));
-
-            if ptr.is_null() {
-                return Err(());
-            }
-
-            let cstr = CStr::from_ptr(ptr);
-
-            Ok(cstr.to_string_lossy())
-        }
+        query_string(self.display_handle().egl_handle(), self.display_handle().raw_display(), name)
     }
 
     pub fn configs<'a>(&'a self) -> Result<Configs<'a, Self>, ()> {
@@ -315,7 +594,10 @@ impl<P: Platform> Display<P> {
     }
 
     pub fn config_search_options_builder(&self) -> ConfigSearchOptionsBuilder {
-        ConfigSearchOptionsBuilder::new(self.egl_version, self.extension_support.clone())
+        ConfigSearchOptionsBuilder::new(
+            self.egl_version(),
+            self.display_extensions().clone(),
+        )
     }
 
     pub fn config_search<'a>(
@@ -421,6 +703,77 @@ impl<P: Platform> Display<P> {
         }
     }
 
+    /// Build OpenGL context without consuming the `Display`.
+    ///
+    /// Context has reference counted handle to the display, so any number
+    /// of contexts can exist at the same time and EGL will be terminated
+    /// only after the last context, surface and `Display` is dropped.
+    ///
+    /// The display handle is taken from the config of the builder, so
+    /// the context always refers to the display it was created with.
+    pub fn build_opengl_context_with_display_handle(
+        &self,
+        builder: OpenGLContextBuilder<P>,
+    ) -> Result<SingleContext<OpenGLContext<P>, Arc<DisplayHandle<P>>>, Option<EGLError>> {
+        let context = builder.build()?;
+        let display_handle = context.display_handle().clone();
+        Ok(SingleContext::new(context, display_handle))
+    }
+
+    /// Extension EGL_KHR_create_context
+    ///
+    /// See `build_opengl_context_with_display_handle` for more information.
+    pub fn build_opengl_context_ext_with_display_handle(
+        &self,
+        builder: OpenGLContextBuilderEXT<P>,
+    ) -> Result<SingleContext<OpenGLContext<P>, Arc<DisplayHandle<P>>>, Option<EGLError>> {
+        let context = builder.build()?;
+        let display_handle = context.display_handle().clone();
+        Ok(SingleContext::new(context, display_handle))
+    }
+
+    /// See `build_opengl_context_with_display_handle` for more information.
+    pub fn build_opengl_es_context_with_display_handle(
+        &self,
+        builder: OpenGLESContextBuilder<P>,
+    ) -> Result<SingleContext<OpenGLESContext<P>, Arc<DisplayHandle<P>>>, Option<EGLError>> {
+        let context = builder.build()?;
+        let display_handle = context.display_handle().clone();
+        Ok(SingleContext::new(context, display_handle))
+    }
+
+    /// Extension EGL_KHR_create_context
+    ///
+    /// See `build_opengl_context_with_display_handle` for more information.
+    pub fn build_opengl_es_context_ext_with_display_handle(
+        &self,
+        builder: OpenGLESContextBuilderEXT<P>,
+    ) -> Result<SingleContext<OpenGLESContext<P>, Arc<DisplayHandle<P>>>, Option<EGLError>> {
+        let context = builder.build()?;
+        let display_handle = context.display_handle().clone();
+        Ok(SingleContext::new(context, display_handle))
+    }
+
+    pub fn build_openvg_context(
+        self,
+        builder: OpenVGContextBuilder<P>,
+    ) -> Result<SingleContext<OpenVGContext<P>, Self>, DisplayError<P, Option<EGLError>>> {
+        match builder.build() {
+            Ok(context) => Ok(SingleContext::new(context, self)),
+            Err(error) => Err(DisplayError::new(self, error)),
+        }
+    }
+
+    /// See `build_opengl_context_with_display_handle` for more information.
+    pub fn build_openvg_context_with_display_handle(
+        &self,
+        builder: OpenVGContextBuilder<P>,
+    ) -> Result<SingleContext<OpenVGContext<P>, Arc<DisplayHandle<P>>>, Option<EGLError>> {
+        let context = builder.build()?;
+        let display_handle = context.display_handle().clone();
+        Ok(SingleContext::new(context, display_handle))
+    }
+
     pub fn display_handle(&self) -> &Arc<DisplayHandle<P>> {
         &self.display_handle
     }
@@ -436,7 +789,7 @@ impl<P: Platform> Display<P> {
     /// Returns `Some(function_loader)` if EGL extension
     /// `EGL_KHR_get_all_proc_addresses` is supported.
     pub fn function_loader(&self) -> Option<FunctionLoader<P>> {
-        match self.extension_support.get_all_proc_addresses {
+        match self.display_extensions().get_all_proc_addresses {
             true => Some(FunctionLoader { display: self }),
             false => None,
         }
@@ -446,6 +799,146 @@ impl<P: Platform> Display<P> {
         Ok(ClientApiSupport::parse(&self.client_apis()?))
     }
 
+    /// Create `EGLImage` from client buffer. Requires EGL 1.5 or
+    /// extension EGL_KHR_image_base.
+    ///
+    /// # Safety
+    ///
+    /// Argument `buffer` must be valid for `target` and `raw_context`. Use
+    /// `ffi::NO_CONTEXT` as `raw_context` if `target` does not require a context.
+    pub unsafe fn create_image(
+        &self,
+        raw_context: ffi::types::EGLContext,
+        target: ffi::types::EGLenum,
+        buffer: ffi::types::EGLClientBuffer,
+        attributes: ImageAttributeList,
+    ) -> Result<EGLImage<P>, ImageCreationError> {
+        EGLImage::new(&self.display_handle, raw_context, target, buffer, attributes)
+    }
+
+    /// Create `EGLImage` from texture or renderbuffer of OpenGL or OpenGL ES
+    /// context.
+    ///
+    /// Returns `ImageCreationError::NotSupported` if display does not
+    /// support EGL 1.5 or the extension required by the `source` and
+    /// `ImageCreationError::DifferentDisplay` if `context` is not from
+    /// this display.
+    pub fn create_gl_image<C: GLContext>(
+        &self,
+        context: &C,
+        source: GLImageSource,
+        attributes: ImageAttributeList,
+    ) -> Result<EGLImage<P>, ImageCreationError> {
+        if context.raw_display() != self.raw_display() {
+            return Err(ImageCreationError::DifferentDisplay);
+        }
+
+        if !source.supported(self) {
+            return Err(ImageCreationError::NotSupported);
+        }
+
+        let mut attributes = attributes;
+        let target = source.add_attributes(&mut attributes);
+
+        unsafe {
+            EGLImage::new(
+                &self.display_handle,
+                context.raw_context(),
+                target,
+                source.client_buffer(),
+                attributes,
+            )
+        }
+    }
+
+    /// Extension EGL_EXT_image_dma_buf_import
+    ///
+    /// Import DMA-BUF file descriptors as `EGLImage`. EGL does not take
+    /// ownership of the file descriptors, so they can be closed after
+    /// the image is created.
+    ///
+    /// Returns `ImageCreationError::NotSupported` if the image requires
+    /// an extension which is not supported and
+    /// `ImageCreationError::InvalidAttributes` if the builder has no planes,
+    /// too many planes or values which do not fit in `EGLint`.
+    #[cfg(unix)]
+    pub fn create_dma_buf_image(
+        &self,
+        builder: DmaBufImageBuilder,
+    ) -> Result<EGLImage<P>, ImageCreationError> {
+        let attributes = builder.build(self.display_extensions())?;
+
+        unsafe {
+            EGLImage::new(
+                &self.display_handle,
+                ffi::NO_CONTEXT,
+                extensions::LINUX_DMA_BUF_EXT,
+                ptr::null_mut::<os::raw::c_void>() as ffi::types::EGLClientBuffer,
+                attributes,
+            )
+        }
+    }
+
+    /// Extension EGL_EXT_image_dma_buf_import_modifiers
+    ///
+    /// Query DRM fourcc formats which can be imported as `EGLImage`.
+    #[cfg(unix)]
+    pub fn dma_buf_formats(&self) -> Result<Vec<u32>, DmaBufQueryError> {
+        if !self.display_extensions().image_dma_buf_import_modifiers() {
+            return Err(DmaBufQueryError::ExtensionNotSupported);
+        }
+
+        dma_buf::query_formats(self.egl_handle(), self.raw_display())
+    }
+
+    /// Extension EGL_EXT_image_dma_buf_import_modifiers
+    ///
+    /// Query format modifiers which can be used when importing
+    /// DRM fourcc format `fourcc` as `EGLImage`.
+    #[cfg(unix)]
+    pub fn dma_buf_modifiers(&self, fourcc: u32) -> Result<Vec<DmaBufModifier>, DmaBufQueryError> {
+        if !self.display_extensions().image_dma_buf_import_modifiers() {
+            return Err(DmaBufQueryError::ExtensionNotSupported);
+        }
+
+        dma_buf::query_modifiers(self.egl_handle(), self.raw_display(), fourcc)
+    }
+
+    /// Extension EGL_ANDROID_native_fence_sync
+    ///
+    /// Create sync object from native fence file descriptor.
+    ///
+    /// If sync object creation succeeds, EGL takes ownership of the file
+    /// descriptor and closes it when the sync object is destroyed. If there
+    /// is an error, the file descriptor is returned back to the caller.
+    #[cfg(unix)]
+    pub fn import_native_fence_fd(
+        &self,
+        fd: OwnedFd,
+    ) -> Result<EGLSync<P>, (OwnedFd, SyncCreationError)> {
+        if !self.display_extensions().android_native_fence_sync() {
+            return Err((fd, SyncCreationError::NotSupported));
+        }
+
+        let mut attributes = AttributeListBuilder::new();
+        attributes.add(
+            extensions::SYNC_NATIVE_FENCE_FD_ANDROID as EGLint,
+            fd.as_raw_fd(),
+        );
+
+        match EGLSync::new(
+            &self.display_handle,
+            extensions::SYNC_NATIVE_FENCE_ANDROID,
+            attributes.build(),
+        ) {
+            Ok(sync) => {
+                // EGL owns the file descriptor now.
+                fd.into_raw_fd();
+                Ok(sync)
+            }
+            Err(error) => Err((fd, error)),
+        }
+    }
 }
 
 use config::client_api::*;
@@ -468,6 +961,35 @@ impl<P: Platform> Display<P> {
         }
     }
 
+    pub fn pbuffer_surface(&self, config: &Config<Self>) -> QueryResult<Option<ConfigPbuffer<P>>> {
+        if config.pbuffer_config()? {
+            Ok(Some(ConfigPbuffer::new(self.to_display_config(config))))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn pixmap_surface(&self, config: &Config<Self>) -> QueryResult<Option<ConfigPixmap<P>>> {
+        if config.pixmap_config()? {
+            Ok(Some(ConfigPixmap::new(self.to_display_config(config))))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn openvg_context_builder(
+        &self,
+        config: &Config<Self>,
+    ) -> QueryResult<Option<OpenVGContextBuilder<P>>> {
+        if config.openvg_config()? {
+            Ok(Some(OpenVGContextBuilder::new(ConfigOpenVG::new(
+                self.to_display_config(config),
+            ))))
+        } else {
+            Ok(None)
+        }
+    }
+
     pub fn opengl_context_builder(
         &self,
         config: &Config<Self>,
@@ -582,6 +1104,24 @@ impl<'a, P: Platform + 'a> FunctionLoader<'a, P> {
     }
 }
 
+fn query_string(
+    _egl_handle: &EGLHandle,
+    raw_display: ffi::types::EGLDisplay,
+    name: EGLint,
+) -> Result<Cow<str>, ()> {
+    unsafe {
+        let ptr = egl_function!(_egl_handle, QueryString(raw_display, name));
+
+        if ptr.is_null() {
+            return Err(());
+        }
+
+        let cstr = CStr::from_ptr(ptr);
+
+        Ok(cstr.to_string_lossy())
+    }
+}
+
 pub(crate) fn get_proc_address(_egl_handle: &EGLHandle, name: &str) -> Result<*const os::raw::c_void, NulError> {
     let c_string = match CString::new(name) {
         Ok(s) => s,
@@ -603,6 +1143,24 @@ impl<T: Platform> DisplayType for Display<T> {
         self.display_handle.raw_display()
     }
 
+    fn egl_version(&self) -> EGLVersion {
+        self.display_handle.egl_version
+    }
+
+    fn display_extensions(&self) -> &DisplayExtensionSupport {
+        &self.display_handle.extension_support
+    }
+
+    fn egl_handle(&self) -> &EGLHandle {
+        self.display_handle.egl_handle()
+    }
+}
+
+impl<T: Platform> DisplayType for Arc<DisplayHandle<T>> {
+    fn raw_display(&self) -> ffi::types::EGLDisplay {
+        DisplayHandle::raw_display(self)
+    }
+
     fn egl_version(&self) -> EGLVersion {
         self.egl_version
     }
@@ -612,7 +1170,7 @@ impl<T: Platform> DisplayType for Display<T> {
     }
 
     fn egl_handle(&self) -> &EGLHandle {
-        self.display_handle.egl_handle()
+        DisplayHandle::egl_handle(self)
     }
 }
 
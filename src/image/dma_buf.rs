@@ -0,0 +1,430 @@
+//! EGL extensions EGL_EXT_image_dma_buf_import,
+//! EGL_EXT_image_dma_buf_import_modifiers and EGL_MESA_image_dma_buf_export.
+
+use std::os::raw::c_int;
+use std::os::unix::io::{FromRawFd, RawFd};
+use std::ptr;
+
+use egl_sys::ffi;
+use egl_sys::ffi::types::{EGLBoolean, EGLint};
+use egl_sys::extensions;
+use egl_sys::extensions::types::EGLuint64KHR;
+
+use display::{DisplayExtensionSupport, DisplayType};
+use error::EGLError;
+use platform::Platform;
+use utils::{AttributeListBuilder, OwnedFd};
+use EGLHandle;
+
+use super::{EGLImage, ImageAttributeList, ImageCreationError};
+
+const MAX_PLANES: usize = 4;
+
+const PLANE_ATTRIBUTES: [[EGLint; 5]; MAX_PLANES] = [
+    [
+        extensions::DMA_BUF_PLANE0_FD_EXT as EGLint,
+        extensions::DMA_BUF_PLANE0_OFFSET_EXT as EGLint,
+        extensions::DMA_BUF_PLANE0_PITCH_EXT as EGLint,
+        extensions::DMA_BUF_PLANE0_MODIFIER_LO_EXT as EGLint,
+        extensions::DMA_BUF_PLANE0_MODIFIER_HI_EXT as EGLint,
+    ],
+    [
+        extensions::DMA_BUF_PLANE1_FD_EXT as EGLint,
+        extensions::DMA_BUF_PLANE1_OFFSET_EXT as EGLint,
+        extensions::DMA_BUF_PLANE1_PITCH_EXT as EGLint,
+        extensions::DMA_BUF_PLANE1_MODIFIER_LO_EXT as EGLint,
+        extensions::DMA_BUF_PLANE1_MODIFIER_HI_EXT as EGLint,
+    ],
+    [
+        extensions::DMA_BUF_PLANE2_FD_EXT as EGLint,
+        extensions::DMA_BUF_PLANE2_OFFSET_EXT as EGLint,
+        extensions::DMA_BUF_PLANE2_PITCH_EXT as EGLint,
+        extensions::DMA_BUF_PLANE2_MODIFIER_LO_EXT as EGLint,
+        extensions::DMA_BUF_PLANE2_MODIFIER_HI_EXT as EGLint,
+    ],
+    [
+        extensions::DMA_BUF_PLANE3_FD_EXT as EGLint,
+        extensions::DMA_BUF_PLANE3_OFFSET_EXT as EGLint,
+        extensions::DMA_BUF_PLANE3_PITCH_EXT as EGLint,
+        extensions::DMA_BUF_PLANE3_MODIFIER_LO_EXT as EGLint,
+        extensions::DMA_BUF_PLANE3_MODIFIER_HI_EXT as EGLint,
+    ],
+];
+
+#[derive(Debug)]
+pub enum DmaBufQueryError {
+    ExtensionNotSupported,
+    EGLError(Option<EGLError>),
+}
+
+#[derive(Debug, Copy, Clone)]
+#[repr(u32)]
+pub enum YuvColorSpaceHint {
+    ITURec601 = extensions::ITU_REC601_EXT,
+    ITURec709 = extensions::ITU_REC709_EXT,
+    ITURec2020 = extensions::ITU_REC2020_EXT,
+}
+
+#[derive(Debug, Copy, Clone)]
+#[repr(u32)]
+pub enum SampleRangeHint {
+    Full = extensions::YUV_FULL_RANGE_EXT,
+    Narrow = extensions::YUV_NARROW_RANGE_EXT,
+}
+
+/// One plane of DMA-BUF image.
+#[derive(Debug, Copy, Clone)]
+pub struct DmaBufPlane {
+    /// DMA-BUF file descriptor. EGL does not take ownership of the
+    /// file descriptor.
+    pub fd: RawFd,
+    /// Offset in bytes from the start of the buffer.
+    pub offset: u32,
+    /// Bytes per row.
+    pub pitch: u32,
+    /// Format modifier. Requires extension EGL_EXT_image_dma_buf_import_modifiers.
+    pub modifier: Option<u64>,
+}
+
+/// Format modifier returned from `Display::dma_buf_modifiers`.
+#[derive(Debug, Copy, Clone)]
+pub struct DmaBufModifier {
+    pub modifier: u64,
+    /// Images with this modifier can only be used with
+    /// target `GL_TEXTURE_EXTERNAL_OES`.
+    pub external_only: bool,
+}
+
+/// Plane of image exported with `EGLImage::export_dma_buf`.
+#[derive(Debug)]
+pub struct ExportedDmaBufPlane {
+    /// DMA-BUF file descriptor owned by the caller. Value is `None` if
+    /// the plane does not have a separate file descriptor and should
+    /// use the file descriptor of an earlier plane.
+    pub fd: Option<OwnedFd>,
+    /// Bytes per row.
+    pub stride: u32,
+    /// Offset in bytes from the start of the buffer.
+    pub offset: u32,
+}
+
+/// Image exported with `EGLImage::export_dma_buf`.
+#[derive(Debug)]
+pub struct ExportedDmaBuf {
+    /// DRM fourcc format code.
+    pub fourcc: u32,
+    pub modifier: u64,
+    pub planes: Vec<ExportedDmaBufPlane>,
+}
+
+impl ExportedDmaBuf {
+    pub fn plane_count(&self) -> usize {
+        self.planes.len()
+    }
+}
+
+/// Builder for `Display::create_dma_buf_image`.
+pub struct DmaBufImageBuilder {
+    width: u32,
+    height: u32,
+    fourcc: u32,
+    planes: Vec<DmaBufPlane>,
+    too_many_planes: bool,
+    color_space_hint: Option<YuvColorSpaceHint>,
+    sample_range_hint: Option<SampleRangeHint>,
+}
+
+impl DmaBufImageBuilder {
+    /// Argument `fourcc` is DRM fourcc format code.
+    pub fn new(width: u32, height: u32, fourcc: u32) -> Self {
+        DmaBufImageBuilder {
+            width,
+            height,
+            fourcc,
+            planes: Vec::with_capacity(MAX_PLANES),
+            too_many_planes: false,
+            color_space_hint: None,
+            sample_range_hint: None,
+        }
+    }
+
+    /// Add next plane. Fourth plane requires extension
+    /// EGL_EXT_image_dma_buf_import_modifiers.
+    ///
+    /// Image can have at most four planes. If more planes are added,
+    /// image creation fails with `ImageCreationError::InvalidAttributes`.
+    pub fn plane(&mut self, plane: DmaBufPlane) -> &mut Self {
+        if self.planes.len() == MAX_PLANES {
+            self.too_many_planes = true;
+        } else {
+            self.planes.push(plane);
+        }
+
+        self
+    }
+
+    /// Color space of YUV image.
+    ///
+    /// Default value: `YuvColorSpaceHint::ITURec601`
+    pub fn yuv_color_space_hint(&mut self, hint: YuvColorSpaceHint) -> &mut Self {
+        self.color_space_hint = Some(hint);
+        self
+    }
+
+    /// Sample range of YUV image.
+    ///
+    /// Default value: `SampleRangeHint::Narrow`
+    pub fn sample_range_hint(&mut self, hint: SampleRangeHint) -> &mut Self {
+        self.sample_range_hint = Some(hint);
+        self
+    }
+
+    pub(crate) fn build(
+        self,
+        extension_support: &DisplayExtensionSupport,
+    ) -> Result<ImageAttributeList, ImageCreationError> {
+        if !extension_support.image_dma_buf_import() {
+            return Err(ImageCreationError::NotSupported);
+        }
+
+        let max_value = EGLint::max_value() as u32;
+
+        let invalid_size = self.width > max_value || self.height > max_value ||
+            self.planes
+                .iter()
+                .any(|plane| plane.offset > max_value || plane.pitch > max_value);
+
+        if self.planes.is_empty() || self.too_many_planes || invalid_size {
+            return Err(ImageCreationError::InvalidAttributes);
+        }
+
+        let modifiers_required = self.planes.len() == MAX_PLANES ||
+            self.planes.iter().any(|plane| plane.modifier.is_some());
+
+        if modifiers_required && !extension_support.image_dma_buf_import_modifiers() {
+            return Err(ImageCreationError::NotSupported);
+        }
+
+        let mut attributes = AttributeListBuilder::new();
+
+        attributes.add(ffi::WIDTH as EGLint, self.width as EGLint);
+        attributes.add(ffi::HEIGHT as EGLint, self.height as EGLint);
+        attributes.add(
+            extensions::LINUX_DRM_FOURCC_EXT as EGLint,
+            self.fourcc as EGLint,
+        );
+
+        for (plane, names) in self.planes.iter().zip(PLANE_ATTRIBUTES.iter()) {
+            attributes.add(names[0], plane.fd);
+            attributes.add(names[1], plane.offset as EGLint);
+            attributes.add(names[2], plane.pitch as EGLint);
+
+            if let Some(modifier) = plane.modifier {
+                attributes.add(names[3], modifier as u32 as EGLint);
+                attributes.add(names[4], (modifier >> 32) as u32 as EGLint);
+            }
+        }
+
+        if let Some(hint) = self.color_space_hint {
+            attributes.add(
+                extensions::YUV_COLOR_SPACE_HINT_EXT as EGLint,
+                hint as EGLint,
+            );
+        }
+
+        if let Some(hint) = self.sample_range_hint {
+            attributes.add(
+                extensions::SAMPLE_RANGE_HINT_EXT as EGLint,
+                hint as EGLint,
+            );
+        }
+
+        Ok(ImageAttributeList(attributes.build()))
+    }
+}
+
+/// Caller must check that extension EGL_EXT_image_dma_buf_import_modifiers
+/// is supported.
+pub(crate) fn query_formats(
+    egl_handle: &EGLHandle,
+    raw_display: ffi::types::EGLDisplay,
+) -> Result<Vec<u32>, DmaBufQueryError> {
+    let functions = &egl_handle.functions.extensions;
+
+    let mut count = 0;
+
+    let result =
+        unsafe { functions.QueryDmaBufFormatsEXT(raw_display, 0, ptr::null_mut(), &mut count) };
+
+    if result == ffi::FALSE {
+        return Err(DmaBufQueryError::EGLError(EGLError::check_errors(egl_handle)));
+    }
+
+    if count <= 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut formats: Vec<EGLint> = Vec::with_capacity(count as usize);
+    let mut new_count = 0;
+
+    unsafe {
+        let result = functions.QueryDmaBufFormatsEXT(
+            raw_display,
+            count,
+            formats.as_mut_slice().as_mut_ptr(),
+            &mut new_count,
+        );
+
+        if result == ffi::FALSE {
+            return Err(DmaBufQueryError::EGLError(EGLError::check_errors(egl_handle)));
+        }
+
+        if new_count < 0 || count < new_count {
+            return Err(DmaBufQueryError::EGLError(None));
+        }
+
+        formats.set_len(new_count as usize);
+    }
+
+    Ok(formats.into_iter().map(|format| format as u32).collect())
+}
+
+/// Caller must check that extension EGL_EXT_image_dma_buf_import_modifiers
+/// is supported.
+pub(crate) fn query_modifiers(
+    egl_handle: &EGLHandle,
+    raw_display: ffi::types::EGLDisplay,
+    fourcc: u32,
+) -> Result<Vec<DmaBufModifier>, DmaBufQueryError> {
+    let functions = &egl_handle.functions.extensions;
+
+    let mut count = 0;
+
+    let result = unsafe {
+        functions.QueryDmaBufModifiersEXT(
+            raw_display,
+            fourcc as EGLint,
+            0,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            &mut count,
+        )
+    };
+
+    if result == ffi::FALSE {
+        return Err(DmaBufQueryError::EGLError(EGLError::check_errors(egl_handle)));
+    }
+
+    if count <= 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut modifiers: Vec<EGLuint64KHR> = Vec::with_capacity(count as usize);
+    let mut external_only: Vec<EGLBoolean> = Vec::with_capacity(count as usize);
+    let mut new_count = 0;
+
+    unsafe {
+        let result = functions.QueryDmaBufModifiersEXT(
+            raw_display,
+            fourcc as EGLint,
+            count,
+            modifiers.as_mut_slice().as_mut_ptr(),
+            external_only.as_mut_slice().as_mut_ptr(),
+            &mut new_count,
+        );
+
+        if result == ffi::FALSE {
+            return Err(DmaBufQueryError::EGLError(EGLError::check_errors(egl_handle)));
+        }
+
+        if new_count < 0 || count < new_count {
+            return Err(DmaBufQueryError::EGLError(None));
+        }
+
+        modifiers.set_len(new_count as usize);
+        external_only.set_len(new_count as usize);
+    }
+
+    let modifiers = modifiers
+        .into_iter()
+        .zip(external_only)
+        .map(|(modifier, external_only)| DmaBufModifier {
+            modifier,
+            external_only: external_only == ffi::TRUE,
+        })
+        .collect();
+
+    Ok(modifiers)
+}
+
+impl<P: Platform> EGLImage<P> {
+    /// Extension EGL_MESA_image_dma_buf_export
+    ///
+    /// Export image as DMA-BUF file descriptors.
+    pub fn export_dma_buf(&self) -> Result<ExportedDmaBuf, DmaBufQueryError> {
+        if !self.display_handle.display_extensions().mesa_image_dma_buf_export() {
+            return Err(DmaBufQueryError::ExtensionNotSupported);
+        }
+
+        let functions = &self.egl_handle().functions.extensions;
+        let raw_image = self.raw_image as extensions::types::EGLImageKHR;
+
+        let mut fourcc: c_int = 0;
+        let mut plane_count: c_int = 0;
+        let mut modifiers: [EGLuint64KHR; MAX_PLANES] = [0; MAX_PLANES];
+
+        let result = unsafe {
+            functions.ExportDMABUFImageQueryMESA(
+                self.raw_display(),
+                raw_image,
+                &mut fourcc,
+                &mut plane_count,
+                modifiers.as_mut_ptr(),
+            )
+        };
+
+        if result == ffi::FALSE {
+            return Err(DmaBufQueryError::EGLError(EGLError::check_errors(self.egl_handle())));
+        }
+
+        if plane_count <= 0 || plane_count as usize > MAX_PLANES {
+            return Err(DmaBufQueryError::EGLError(None));
+        }
+
+        let mut fds: [c_int; MAX_PLANES] = [-1; MAX_PLANES];
+        let mut strides: [EGLint; MAX_PLANES] = [0; MAX_PLANES];
+        let mut offsets: [EGLint; MAX_PLANES] = [0; MAX_PLANES];
+
+        let result = unsafe {
+            functions.ExportDMABUFImageMESA(
+                self.raw_display(),
+                raw_image,
+                fds.as_mut_ptr(),
+                strides.as_mut_ptr(),
+                offsets.as_mut_ptr(),
+            )
+        };
+
+        if result == ffi::FALSE {
+            return Err(DmaBufQueryError::EGLError(EGLError::check_errors(self.egl_handle())));
+        }
+
+        let planes = (0..plane_count as usize)
+            .map(|i| ExportedDmaBufPlane {
+                fd: if fds[i] < 0 {
+                    None
+                } else {
+                    Some(unsafe { OwnedFd::from_raw_fd(fds[i]) })
+                },
+                stride: strides[i] as u32,
+                offset: offsets[i] as u32,
+            })
+            .collect();
+
+        Ok(ExportedDmaBuf {
+            fourcc: fourcc as u32,
+            modifier: modifiers[0],
+            planes,
+        })
+    }
+}
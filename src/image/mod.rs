@@ -0,0 +1,280 @@
+//! EGLImage
+//!
+//! Uses EGL 1.5 functions if display supports EGL 1.5, otherwise
+//! extension EGL_KHR_image_base is required.
+
+#[cfg(unix)]
+pub mod dma_buf;
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use egl_sys::ffi;
+use egl_sys::ffi::types::{EGLenum, EGLint};
+use egl_sys::extensions;
+
+use display::{DisplayHandle, DisplayType, EGLVersion};
+use error::EGLError;
+use platform::Platform;
+use utils::{AttributeList, AttributeListBuilder};
+use EGLHandle;
+
+#[derive(Debug, Copy, Clone)]
+enum ImageFunctions {
+    EGL15,
+    KHR,
+}
+
+#[derive(Debug)]
+pub enum ImageCreationError {
+    /// EGL 1.5 or required extension is not supported.
+    NotSupported,
+    /// Context is not from the same display.
+    DifferentDisplay,
+    /// Image attributes are invalid. For example DMA-BUF image does not
+    /// have any planes or image size does not fit in `EGLint`.
+    InvalidAttributes,
+    EGLError(Option<EGLError>),
+}
+
+#[derive(Debug, Copy, Clone)]
+#[repr(u32)]
+pub enum CubeMapFace {
+    PositiveX = ffi::GL_TEXTURE_CUBE_MAP_POSITIVE_X,
+    NegativeX = ffi::GL_TEXTURE_CUBE_MAP_NEGATIVE_X,
+    PositiveY = ffi::GL_TEXTURE_CUBE_MAP_POSITIVE_Y,
+    NegativeY = ffi::GL_TEXTURE_CUBE_MAP_NEGATIVE_Y,
+    PositiveZ = ffi::GL_TEXTURE_CUBE_MAP_POSITIVE_Z,
+    NegativeZ = ffi::GL_TEXTURE_CUBE_MAP_NEGATIVE_Z,
+}
+
+/// OpenGL or OpenGL ES object for `Display::create_gl_image`.
+///
+/// Values of `texture` and `renderbuffer` fields are object names.
+#[derive(Debug, Copy, Clone)]
+pub enum GLImageSource {
+    /// Extension EGL_KHR_gl_texture_2D_image
+    Texture2D { texture: u32, mip_level: EGLint },
+    /// Extension EGL_KHR_gl_texture_cubemap_image
+    TextureCubeMap {
+        texture: u32,
+        face: CubeMapFace,
+        mip_level: EGLint,
+    },
+    /// Extension EGL_KHR_gl_texture_3D_image
+    Texture3D {
+        texture: u32,
+        mip_level: EGLint,
+        z_offset: EGLint,
+    },
+    /// Extension EGL_KHR_gl_renderbuffer_image
+    Renderbuffer { renderbuffer: u32 },
+}
+
+impl GLImageSource {
+    /// EGL 1.5 supports all sources without extensions.
+    pub(crate) fn supported<D: DisplayType>(&self, display: &D) -> bool {
+        if let EGLVersion::EGL_1_5 = display.egl_version() {
+            return true;
+        }
+
+        let extensions = display.display_extensions();
+
+        match *self {
+            GLImageSource::Texture2D { .. } => extensions.gl_texture_2d_image(),
+            GLImageSource::TextureCubeMap { .. } => extensions.gl_texture_cubemap_image(),
+            GLImageSource::Texture3D { .. } => extensions.gl_texture_3d_image(),
+            GLImageSource::Renderbuffer { .. } => extensions.gl_renderbuffer_image(),
+        }
+    }
+
+    pub(crate) fn client_buffer(&self) -> ffi::types::EGLClientBuffer {
+        let name = match *self {
+            GLImageSource::Texture2D { texture, .. } |
+            GLImageSource::TextureCubeMap { texture, .. } |
+            GLImageSource::Texture3D { texture, .. } => texture,
+            GLImageSource::Renderbuffer { renderbuffer } => renderbuffer,
+        };
+
+        name as usize as ffi::types::EGLClientBuffer
+    }
+
+    /// Adds source specific attributes and returns the image target.
+    pub(crate) fn add_attributes(&self, attributes: &mut ImageAttributeList) -> EGLenum {
+        match *self {
+            GLImageSource::Texture2D { mip_level, .. } => {
+                attributes.0.add(ffi::GL_TEXTURE_LEVEL as EGLint, mip_level);
+                ffi::GL_TEXTURE_2D
+            }
+            GLImageSource::TextureCubeMap {
+                face, mip_level, ..
+            } => {
+                attributes.0.add(ffi::GL_TEXTURE_LEVEL as EGLint, mip_level);
+                face as EGLenum
+            }
+            GLImageSource::Texture3D {
+                mip_level,
+                z_offset,
+                ..
+            } => {
+                attributes.0.add(ffi::GL_TEXTURE_LEVEL as EGLint, mip_level);
+                attributes.0.add(ffi::GL_TEXTURE_ZOFFSET as EGLint, z_offset);
+                ffi::GL_TEXTURE_3D
+            }
+            GLImageSource::Renderbuffer { .. } => ffi::GL_RENDERBUFFER,
+        }
+    }
+}
+
+pub struct ImageAttributeListBuilder {
+    attributes: AttributeListBuilder,
+}
+
+impl ImageAttributeListBuilder {
+    pub fn new() -> Self {
+        ImageAttributeListBuilder {
+            attributes: AttributeListBuilder::new(),
+        }
+    }
+
+    /// Preserve contents of the image source.
+    ///
+    /// Default value: false
+    pub fn preserved(&mut self, value: bool) -> &mut Self {
+        let value = if value { ffi::TRUE } else { ffi::FALSE };
+
+        self.attributes
+            .add(ffi::IMAGE_PRESERVED as EGLint, value as EGLint);
+        self
+    }
+
+    pub fn build(self) -> ImageAttributeList {
+        ImageAttributeList(self.attributes.build())
+    }
+}
+
+pub struct ImageAttributeList(AttributeList);
+
+impl ImageAttributeList {
+    pub fn ptr(&self) -> *const EGLint {
+        self.0.ptr()
+    }
+}
+
+impl Default for ImageAttributeList {
+    fn default() -> Self {
+        ImageAttributeList(AttributeList::empty())
+    }
+}
+
+/// Image which will be destroyed when dropped.
+///
+/// Image has reference counted handle to the display, so display
+/// will not be terminated before image is dropped.
+///
+/// Use `raw_image` with functions like `glEGLImageTargetTexture2DOES`.
+#[derive(Debug)]
+pub struct EGLImage<P: Platform> {
+    display_handle: Arc<DisplayHandle<P>>,
+    raw_image: ffi::types::EGLImage,
+    functions: ImageFunctions,
+    _marker: PhantomData<ffi::types::EGLImage>,
+}
+
+// Images can be used from multiple threads.
+unsafe impl<P: Platform + Send + Sync> Send for EGLImage<P> {}
+unsafe impl<P: Platform + Send + Sync> Sync for EGLImage<P> {}
+
+impl<P: Platform> EGLImage<P> {
+    /// Argument `buffer` must be valid for `target` and `raw_context`.
+    pub(crate) unsafe fn new(
+        display_handle: &Arc<DisplayHandle<P>>,
+        raw_context: ffi::types::EGLContext,
+        target: EGLenum,
+        buffer: ffi::types::EGLClientBuffer,
+        attribute_list: ImageAttributeList,
+    ) -> Result<Self, ImageCreationError> {
+        let functions = match display_handle.egl_version() {
+            EGLVersion::EGL_1_5 => ImageFunctions::EGL15,
+            EGLVersion::EGL_1_4 if display_handle.display_extensions().image_base() => {
+                ImageFunctions::KHR
+            }
+            EGLVersion::EGL_1_4 => return Err(ImageCreationError::NotSupported),
+        };
+
+        let egl_handle = DisplayHandle::egl_handle(display_handle);
+        let raw_display = DisplayHandle::raw_display(display_handle);
+
+        let raw_image = match functions {
+            ImageFunctions::EGL15 => {
+                let attribute_list = attribute_list.0.to_attrib_list();
+
+                egl_function!(
+                    egl_handle,
+                    CreateImage(
+                        raw_display,
+                        raw_context,
+                        target,
+                        buffer,
+                        attribute_list.as_ptr()
+                    )
+                )
+            }
+            ImageFunctions::KHR => egl_handle.functions.extensions.CreateImageKHR(
+                raw_display,
+                raw_context,
+                target,
+                buffer,
+                attribute_list.ptr(),
+            ) as ffi::types::EGLImage,
+        };
+
+        if raw_image == ffi::NO_IMAGE {
+            return Err(ImageCreationError::EGLError(EGLError::check_errors(egl_handle)));
+        }
+
+        Ok(EGLImage {
+            display_handle: display_handle.clone(),
+            raw_image,
+            functions,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn raw_image(&self) -> ffi::types::EGLImage {
+        self.raw_image
+    }
+
+    pub fn raw_display(&self) -> ffi::types::EGLDisplay {
+        self.display_handle.raw_display()
+    }
+
+    pub fn display_handle(&self) -> &Arc<DisplayHandle<P>> {
+        &self.display_handle
+    }
+
+    fn egl_handle(&self) -> &EGLHandle {
+        self.display_handle.egl_handle()
+    }
+}
+
+impl<P: Platform> Drop for EGLImage<P> {
+    fn drop(&mut self) {
+        let result = match self.functions {
+            ImageFunctions::EGL15 => unsafe {
+                egl_function!(self.egl_handle(), DestroyImage(self.raw_display(), self.raw_image))
+            },
+            ImageFunctions::KHR => unsafe {
+                self.egl_handle().functions.extensions.DestroyImageKHR(
+                    self.raw_display(),
+                    self.raw_image as extensions::types::EGLImageKHR,
+                )
+            },
+        };
+
+        if result == ffi::FALSE {
+            let error = EGLError::check_errors(self.egl_handle());
+            eprintln!("egl_wrapper: couldn't destroy image, error: {:?}", error);
+        }
+    }
+}
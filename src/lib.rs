@@ -32,6 +32,11 @@ pub mod display;
 pub mod surface;
 pub mod context;
 pub mod platform;
+pub mod device;
+pub mod sync;
+pub mod image;
+#[cfg(feature = "wayland")]
+pub mod wayland;
 
 pub use egl_sys::ffi;
 
@@ -43,11 +48,13 @@ use std::io;
 use std::os::raw::c_void;
 
 use egl_sys::extensions;
-use egl_sys::ffi::types::EGLint;
+use egl_sys::ffi::types::{EGLenum, EGLint};
 
 use display::{Display, DisplayCreationError};
+use device::{DeviceQueryError, EGLDevice};
 use error::EGLError;
-use platform::{EXTPlatform, EXTPlatformType, DefaultPlatform, EXTPlatformAttributeList};
+use platform::{EXTPlatform, EXTPlatformType, DefaultPlatform, EXTPlatformAttributeList,
+               SurfacelessPlatform};
 
 lazy_static! {
     static ref INIT_FLAG: Mutex<bool> = Mutex::new(false);
@@ -78,6 +85,10 @@ pub(crate) struct EGLFunctions {
     pub(crate) extensions: extensions::Egl,
 }
 
+// Function pointers are not modified after loading them.
+unsafe impl Send for EGLFunctions {}
+unsafe impl Sync for EGLFunctions {}
+
 #[cfg(all(unix, feature = "runtime-linking", not(feature = "raspberry-pi-broadcom")))]
 const EGL_LIBRARY_NAME: &'static str = "libEGL.so.1";
 
@@ -185,13 +196,35 @@ impl EGLHandle {
     pub fn display_builder(&self) -> DisplayBuilder {
         DisplayBuilder::new(self.clone())
     }
+
+    /// List EGL devices.
+    ///
+    /// Requires extensions EGL_EXT_device_enumeration and EGL_EXT_device_query
+    /// or extension EGL_EXT_device_base.
+    pub fn devices(&self) -> Result<Vec<EGLDevice>, DeviceQueryError> {
+        let display_builder = self.display_builder();
+
+        if !display_builder.ext_device_enumeration() || !display_builder.ext_device_query() {
+            return Err(DeviceQueryError::ExtensionNotSupported);
+        }
+
+        EGLDevice::query_devices(self)
+    }
 }
 
 
 #[derive(Debug)]
 struct ClientExtensions {
+    ext_platform_base: bool,
     ext_platform_x11: bool,
     ext_platform_wayland: bool,
+    ext_platform_xcb: bool,
+    khr_platform_gbm: bool,
+    mesa_platform_gbm: bool,
+    mesa_platform_surfaceless: bool,
+    ext_device_enumeration: bool,
+    ext_device_query: bool,
+    ext_platform_device: bool,
 }
 
 impl ClientExtensions {
@@ -206,14 +239,46 @@ impl ClientExtensions {
                         !egl_handle.functions.extensions.CreatePlatformPixmapSurfaceEXT.is_loaded() {
                             return Err(())
                     }
+
+                    extensions.ext_platform_base = true;
                 }
                 "EGL_EXT_platform_x11" => extensions.ext_platform_x11 = true,
                 "EGL_EXT_platform_wayland" => extensions.ext_platform_wayland = true,
+                "EGL_EXT_platform_xcb" => extensions.ext_platform_xcb = true,
+                "EGL_KHR_platform_gbm" => extensions.khr_platform_gbm = true,
+                "EGL_MESA_platform_gbm" => extensions.mesa_platform_gbm = true,
+                "EGL_MESA_platform_surfaceless" => extensions.mesa_platform_surfaceless = true,
+                "EGL_EXT_device_base" => {
+                    extensions.ext_device_enumeration = true;
+                    extensions.ext_device_query = true;
+                }
+                "EGL_EXT_device_enumeration" => extensions.ext_device_enumeration = true,
+                "EGL_EXT_device_query" => extensions.ext_device_query = true,
+                "EGL_EXT_platform_device" => extensions.ext_platform_device = true,
                 _ => (),
             }
         }
 
-        // TODO: If platform extension is found, require EGL_EXT_platform_base.
+        // Platform extensions require EGL_EXT_platform_base.
+        if !extensions.ext_platform_base {
+            extensions.ext_platform_x11 = false;
+            extensions.ext_platform_wayland = false;
+            extensions.ext_platform_xcb = false;
+            extensions.khr_platform_gbm = false;
+            extensions.mesa_platform_gbm = false;
+            extensions.mesa_platform_surfaceless = false;
+            extensions.ext_platform_device = false;
+        }
+
+        if extensions.ext_device_enumeration &&
+            !egl_handle.functions.extensions.QueryDevicesEXT.is_loaded() {
+                extensions.ext_device_enumeration = false;
+        }
+
+        if extensions.ext_device_query &&
+            !egl_handle.functions.extensions.QueryDeviceStringEXT.is_loaded() {
+                extensions.ext_device_query = false;
+        }
 
         Ok(extensions)
     }
@@ -222,8 +287,16 @@ impl ClientExtensions {
 impl Default for ClientExtensions {
     fn default() -> Self {
         ClientExtensions {
+            ext_platform_base: false,
             ext_platform_x11: false,
             ext_platform_wayland: false,
+            ext_platform_xcb: false,
+            khr_platform_gbm: false,
+            mesa_platform_gbm: false,
+            mesa_platform_surfaceless: false,
+            ext_device_enumeration: false,
+            ext_device_query: false,
+            ext_platform_device: false,
         }
     }
 }
@@ -307,6 +380,69 @@ impl DisplayBuilder {
         }
     }
 
+    /// Extension EGL_EXT_platform_xcb
+    pub fn ext_platform_xcb(&self) -> bool {
+        if let Some(ref extensions) = self.client_extensions {
+            extensions.ext_platform_xcb
+        } else {
+            false
+        }
+    }
+
+    /// Extension EGL_KHR_platform_gbm or EGL_MESA_platform_gbm
+    pub fn platform_gbm(&self) -> bool {
+        if let Some(ref extensions) = self.client_extensions {
+            extensions.khr_platform_gbm || extensions.mesa_platform_gbm
+        } else {
+            false
+        }
+    }
+
+    /// Extension EGL_MESA_platform_surfaceless
+    pub fn mesa_platform_surfaceless(&self) -> bool {
+        if let Some(ref extensions) = self.client_extensions {
+            extensions.mesa_platform_surfaceless
+        } else {
+            false
+        }
+    }
+
+    /// Extension EGL_EXT_device_enumeration
+    pub fn ext_device_enumeration(&self) -> bool {
+        if let Some(ref extensions) = self.client_extensions {
+            extensions.ext_device_enumeration
+        } else {
+            false
+        }
+    }
+
+    /// Extension EGL_EXT_device_query
+    pub fn ext_device_query(&self) -> bool {
+        if let Some(ref extensions) = self.client_extensions {
+            extensions.ext_device_query
+        } else {
+            false
+        }
+    }
+
+    /// Extension EGL_EXT_platform_device
+    pub fn ext_platform_device(&self) -> bool {
+        if let Some(ref extensions) = self.client_extensions {
+            extensions.ext_platform_device
+        } else {
+            false
+        }
+    }
+
+    fn ext_platform_type_supported(&self, display_type: EXTPlatformType) -> bool {
+        match display_type {
+            EXTPlatformType::X11 => self.ext_platform_x11(),
+            EXTPlatformType::Wayland => self.ext_platform_wayland(),
+            EXTPlatformType::XCB => self.ext_platform_xcb(),
+            EXTPlatformType::GBM => self.platform_gbm(),
+        }
+    }
+
     pub unsafe fn build_ext_platform_base_display<T>(
         self,
         display_type: EXTPlatformType,
@@ -314,14 +450,72 @@ impl DisplayBuilder {
         native: T,
         attributes: Option<EXTPlatformAttributeList>,
     ) -> Result<Display<EXTPlatform<T>>, (Self, DisplayCreationError)> {
-        // TODO: check client extension support
+        if !self.ext_platform_type_supported(display_type) {
+            return Err((self, DisplayCreationError::PlatformExtensionNotSupported));
+        }
 
         EXTPlatform::get_display(
-            display_type,
+            display_type as EGLenum,
             native_display_ptr,
             native,
             attributes.unwrap_or_default(),
             self.egl_handle.clone()
         ).map_err(|e| (self, e))
     }
+
+    /// Build display from `gbm_device` using extension EGL_KHR_platform_gbm
+    /// or EGL_MESA_platform_gbm.
+    ///
+    /// Argument `native` is stored to the display's `EXTPlatform`, so it can
+    /// own the `gbm_device` and keep it alive as long as the display exists.
+    ///
+    /// # Safety
+    ///
+    /// Argument `gbm_device` must be a valid `gbm_device` pointer.
+    pub unsafe fn build_gbm_platform_display<T>(
+        self,
+        gbm_device: *mut c_void,
+        native: T,
+        attributes: Option<EXTPlatformAttributeList>,
+    ) -> Result<Display<EXTPlatform<T>>, (Self, DisplayCreationError)> {
+        self.build_ext_platform_base_display(EXTPlatformType::GBM, gbm_device, native, attributes)
+    }
+
+    /// Build display without native display using extension
+    /// EGL_MESA_platform_surfaceless.
+    pub fn build_surfaceless_platform_display(
+        self,
+        attributes: Option<EXTPlatformAttributeList>,
+    ) -> Result<Display<SurfacelessPlatform>, (Self, DisplayCreationError)> {
+        if !self.mesa_platform_surfaceless() {
+            return Err((self, DisplayCreationError::PlatformExtensionNotSupported));
+        }
+
+        SurfacelessPlatform::get_display(
+            attributes.unwrap_or_default(),
+            self.egl_handle.clone()
+        ).map_err(|e| (self, e))
+    }
+
+    /// Build display for specific `EGLDevice` using extension
+    /// EGL_EXT_platform_device.
+    pub fn build_device_platform_display(
+        self,
+        device: EGLDevice,
+        attributes: Option<EXTPlatformAttributeList>,
+    ) -> Result<Display<EXTPlatform<EGLDevice>>, (Self, DisplayCreationError)> {
+        if !self.ext_platform_device() {
+            return Err((self, DisplayCreationError::PlatformExtensionNotSupported));
+        }
+
+        let raw_device = device.raw_device() as *mut c_void;
+
+        EXTPlatform::get_display(
+            extensions::PLATFORM_DEVICE_EXT,
+            raw_device,
+            device,
+            attributes.unwrap_or_default(),
+            self.egl_handle.clone()
+        ).map_err(|e| (self, e))
+    }
 }
@@ -2,18 +2,24 @@
 
 use utils::AttributeList;
 use std::os::raw::c_void;
+use std::ptr;
 
-use egl_sys::ffi::types::{EGLenum, EGLint, NativeDisplayType, NativeWindowType};
+use egl_sys::ffi::types::{EGLenum, EGLint, NativeDisplayType, NativePixmapType, NativeWindowType};
 use egl_sys::ffi;
 use egl_sys::extensions;
 
 use display::{Display, DisplayCreationError};
-use utils::AttributeListBuilder;
+use utils::{AttributeListBuilder, UnsignedInteger};
 use surface::window::{WindowSurface, WindowSurfaceAttributeList};
+use surface::pixmap::{PixmapSurface, PixmapSurfaceAttributeList};
 use error::EGLError;
 use config::client_api::*;
 use EGLHandle;
 
+/// XCB window id `xcb_window_t`.
+#[allow(non_camel_case_types)]
+pub type xcb_window_t = u32;
+
 pub trait Platform: Sized {
     fn egl_handle(&self) -> &EGLHandle;
 }
@@ -70,6 +76,31 @@ impl<T> DefaultPlatform<T> {
         ))
     }
 
+    pub unsafe fn get_platform_pixmap_surface<W>(
+        &self,
+        optional_native_pixmap_handle: W,
+        raw_native_pixmap: NativePixmapType,
+        config_pixmap: ConfigPixmap<Self>,
+        attribute_list: PixmapSurfaceAttributeList,
+    ) -> Result<PixmapSurface<W, Self>, PixmapCreationError> {
+        let raw_surface = egl_function!(self.egl_handle, CreatePixmapSurface(
+            config_pixmap.display_config().raw_display(),
+            config_pixmap.display_config().raw_config(),
+            raw_native_pixmap,
+            attribute_list.ptr()
+        ));
+
+        if raw_surface == ffi::NO_SURFACE {
+            return Err(PixmapCreationError::EGLError(EGLError::check_errors(&self.egl_handle)));
+        }
+
+        Ok(PixmapSurface::new(
+            optional_native_pixmap_handle,
+            config_pixmap,
+            raw_surface,
+        ))
+    }
+
     pub fn optional_native_display(&self) -> &T {
         &self.optional_native_display_handle
     }
@@ -92,16 +123,29 @@ pub struct EXTPlatform<T> {
     egl_handle: EGLHandle,
 }
 
-#[derive(Debug)]
+/// Platforms which are used with a native display pointer.
+///
+/// Surfaceless and device platforms don't have a native display, see
+/// `DisplayBuilder::build_surfaceless_platform_display` and
+/// `DisplayBuilder::build_device_platform_display`.
+#[derive(Debug, Copy, Clone)]
 #[repr(u32)]
 pub enum EXTPlatformType {
     X11 = extensions::PLATFORM_X11_EXT,
     Wayland = extensions::PLATFORM_WAYLAND_EXT,
+    /// EGL_EXT_platform_xcb
+    ///
+    /// Native display is `xcb_connection_t`.
+    XCB = extensions::PLATFORM_XCB_EXT,
+    /// EGL_KHR_platform_gbm or EGL_MESA_platform_gbm
+    ///
+    /// Native display is `gbm_device`.
+    GBM = extensions::PLATFORM_GBM_KHR,
 }
 
 impl<T> EXTPlatform<T> {
     pub(crate) fn get_display(
-        platform_type: EXTPlatformType,
+        platform: EGLenum,
         ptr_to_native_display: *mut c_void,
         optional_native_display_handle: T,
         attribute_list: EXTPlatformAttributeList,
@@ -109,7 +153,7 @@ impl<T> EXTPlatform<T> {
     ) -> Result<Display<Self>, DisplayCreationError> {
         let raw_display = unsafe {
             egl_handle.functions.extensions.GetPlatformDisplayEXT(
-                platform_type as EGLenum,
+                platform,
                 ptr_to_native_display,
                 attribute_list.ptr(),
             )
@@ -152,6 +196,83 @@ impl<T> EXTPlatform<T> {
         ))
     }
 
+    pub unsafe fn get_platform_pixmap_surface<W>(
+        &self,
+        optional_native_pixmap_handle: W,
+        raw_native_pixmap: *mut c_void,
+        config_pixmap: ConfigPixmap<Self>,
+        attribute_list: PixmapSurfaceAttributeList,
+    ) -> Result<PixmapSurface<W, Self>, PixmapCreationError> {
+        let raw_surface = self.egl_handle.functions.extensions.CreatePlatformPixmapSurfaceEXT(
+            config_pixmap.display_config().raw_display(),
+            config_pixmap.display_config().raw_config(),
+            raw_native_pixmap,
+            attribute_list.ptr(),
+        );
+
+        if raw_surface == ffi::NO_SURFACE {
+            return Err(PixmapCreationError::EGLError(EGLError::check_errors(&self.egl_handle)));
+        }
+
+        Ok(PixmapSurface::new(
+            optional_native_pixmap_handle,
+            config_pixmap,
+            raw_surface,
+        ))
+    }
+
+    /// Create window surface from XCB window.
+    ///
+    /// # Safety
+    ///
+    /// Display must be created with `EXTPlatformType::XCB` and
+    /// `xcb_window` must be a valid window of the same XCB connection.
+    pub unsafe fn get_xcb_window_surface<W>(
+        &self,
+        optional_native_window_handle: W,
+        xcb_window: xcb_window_t,
+        config_window: ConfigWindow<Self>,
+        attribute_list: WindowSurfaceAttributeList,
+    ) -> Result<WindowSurface<W, Self>, WindowCreationError> {
+        // Native window for EGL_EXT_platform_xcb is a pointer to xcb_window_t.
+        let mut xcb_window = xcb_window;
+        let raw_native_window = &mut xcb_window as *mut xcb_window_t as *mut c_void;
+
+        self.get_platform_window_surface(
+            optional_native_window_handle,
+            raw_native_window,
+            config_window,
+            attribute_list,
+        )
+    }
+
+    /// Create window surface from `gbm_surface`.
+    ///
+    /// Argument `optional_native_window_handle` is stored to the
+    /// `WindowSurface`, so it can own the `gbm_surface` and destroy it
+    /// after the EGL surface is destroyed. EGL destroys a current surface
+    /// only when it is released, so release the surface with
+    /// `make_not_current` before dropping it.
+    ///
+    /// # Safety
+    ///
+    /// Display must be created with `EXTPlatformType::GBM` and `gbm_surface`
+    /// must be a valid `gbm_surface` of the same `gbm_device`.
+    pub unsafe fn get_gbm_window_surface<W>(
+        &self,
+        optional_native_window_handle: W,
+        gbm_surface: *mut c_void,
+        config_window: ConfigWindow<Self>,
+        attribute_list: WindowSurfaceAttributeList,
+    ) -> Result<WindowSurface<W, Self>, WindowCreationError> {
+        self.get_platform_window_surface(
+            optional_native_window_handle,
+            gbm_surface,
+            config_window,
+            attribute_list,
+        )
+    }
+
     pub fn optional_native_display(&self) -> &T {
         &self.optional_native_display_handle
     }
@@ -161,20 +282,46 @@ impl<T> EXTPlatform<T> {
     }
 }
 
+/// Attributes for `DisplayBuilder::build_ext_platform_base_display`.
+///
+/// Extension EGL_EXT_platform_wayland doesn't define any attributes, so
+/// Wayland displays can be created with `EXTPlatformAttributeList::default()`.
 pub struct EXTPlatformAttributeListBuilder(AttributeListBuilder);
 
 impl EXTPlatformAttributeListBuilder {
     pub fn new() -> Self {
         EXTPlatformAttributeListBuilder(AttributeListBuilder::new())
     }
-}
 
-pub struct EXTPlatformAttributeList(AttributeList);
+    /// Extension EGL_EXT_platform_x11
+    ///
+    /// Screen of the X11 display. Use this with `EXTPlatformType::X11`.
+    ///
+    /// Default value: default screen of the X11 display
+    pub fn x11_screen(&mut self, screen: UnsignedInteger) -> &mut Self {
+        self.0
+            .add(extensions::PLATFORM_X11_SCREEN_EXT as EGLint, screen.value());
+        self
+    }
 
-impl EXTPlatformAttributeList {
-    // TODO: X11 attribute
+    /// Extension EGL_EXT_platform_xcb
+    ///
+    /// Screen of the XCB connection. Use this with `EXTPlatformType::XCB`.
+    ///
+    /// Default value: default screen of the XCB connection
+    pub fn xcb_screen(&mut self, screen: UnsignedInteger) -> &mut Self {
+        self.0
+            .add(extensions::PLATFORM_XCB_SCREEN_EXT as EGLint, screen.value());
+        self
+    }
+
+    pub fn build(self) -> EXTPlatformAttributeList {
+        EXTPlatformAttributeList(self.0.build())
+    }
 }
 
+pub struct EXTPlatformAttributeList(AttributeList);
+
 impl EXTPlatformAttributeList {
     pub fn ptr(&self) -> *const EGLint {
         self.0.ptr()
@@ -193,8 +340,51 @@ impl<T> Platform for EXTPlatform<T> {
     }
 }
 
+#[derive(Debug)]
+/// EGL extension EGL_MESA_platform_surfaceless platform.
+///
+/// Platform doesn't have a native display, native windows or native pixmaps.
+pub struct SurfacelessPlatform {
+    egl_handle: EGLHandle,
+}
+
+impl SurfacelessPlatform {
+    pub(crate) fn get_display(
+        attribute_list: EXTPlatformAttributeList,
+        egl_handle: EGLHandle,
+    ) -> Result<Display<Self>, DisplayCreationError> {
+        // Native display must be EGL_DEFAULT_DISPLAY.
+        let raw_display = unsafe {
+            egl_handle.functions.extensions.GetPlatformDisplayEXT(
+                extensions::PLATFORM_SURFACELESS_MESA,
+                ptr::null_mut(),
+                attribute_list.ptr(),
+            )
+        };
+
+        if raw_display == ffi::NO_DISPLAY {
+            return Err(DisplayCreationError::NoMatchingDisplay);
+        }
+
+        let platform = SurfacelessPlatform { egl_handle };
+
+        Ok(Display::new(raw_display, platform)?)
+    }
+}
+
+impl Platform for SurfacelessPlatform {
+    fn egl_handle(&self) -> &EGLHandle {
+        &self.egl_handle
+    }
+}
+
 #[derive(Debug)]
 pub enum WindowCreationError {
     NativeWindowNotFound,
     EGLError(Option<EGLError>),
 }
+
+#[derive(Debug)]
+pub enum PixmapCreationError {
+    EGLError(Option<EGLError>),
+}
@@ -1,6 +1,8 @@
 use egl_sys::ffi;
 use egl_sys::ffi::types::{EGLenum, EGLint};
+use egl_sys::extensions;
 
+use display::{DisplayExtensionSupport, DisplayType, EGLVersion};
 use error::EGLError;
 use utils::{PositiveInteger, QueryError, QueryResult, UnsignedInteger};
 
@@ -32,12 +34,130 @@ pub enum TextureTarget {
 
 #[derive(Debug)]
 #[repr(u32)]
+/// OpenVG
+pub enum VgColorspace {
+    SRGB = ffi::VG_COLORSPACE_sRGB,
+    Linear = ffi::VG_COLORSPACE_LINEAR,
+}
+
+#[derive(Debug)]
+#[repr(u32)]
+/// OpenVG
+pub enum VgAlphaFormat {
+    NonPremultiplied = ffi::VG_ALPHA_FORMAT_NONPRE,
+    Premultiplied = ffi::VG_ALPHA_FORMAT_PRE,
+}
+
+#[derive(Debug, Copy, Clone)]
+#[repr(u32)]
+/// OpenGL and OpenGL ES
+///
+/// Requires EGL 1.5 or extension EGL_KHR_gl_colorspace. Wide gamut
+/// and HDR colorspaces require also the extension named in the variant
+/// documentation.
+pub enum GLColorspace {
+    SRGB = ffi::GL_COLORSPACE_SRGB,
+    Linear = ffi::GL_COLORSPACE_LINEAR,
+    /// Extension EGL_EXT_gl_colorspace_bt2020_linear
+    BT2020Linear = extensions::GL_COLORSPACE_BT2020_LINEAR_EXT,
+    /// Extension EGL_EXT_gl_colorspace_bt2020_pq
+    BT2020PQ = extensions::GL_COLORSPACE_BT2020_PQ_EXT,
+    /// Extension EGL_EXT_gl_colorspace_bt2020_hlg
+    BT2020HLG = extensions::GL_COLORSPACE_BT2020_HLG_EXT,
+    /// Extension EGL_EXT_gl_colorspace_scrgb
+    SCRGB = extensions::GL_COLORSPACE_SCRGB_EXT,
+    /// Extension EGL_EXT_gl_colorspace_scrgb_linear
+    SCRGBLinear = extensions::GL_COLORSPACE_SCRGB_LINEAR_EXT,
+    /// Extension EGL_EXT_gl_colorspace_display_p3
+    DisplayP3 = extensions::GL_COLORSPACE_DISPLAY_P3_EXT,
+    /// Extension EGL_EXT_gl_colorspace_display_p3_linear
+    DisplayP3Linear = extensions::GL_COLORSPACE_DISPLAY_P3_LINEAR_EXT,
+}
+
+impl GLColorspace {
+    pub(crate) fn supported<D: DisplayType>(&self, display: &D) -> bool {
+        let extensions = display.display_extensions();
+
+        let gl_colorspace = match display.egl_version() {
+            EGLVersion::EGL_1_5 => true,
+            EGLVersion::EGL_1_4 => extensions.gl_colorspace(),
+        };
+
+        gl_colorspace && match *self {
+            GLColorspace::SRGB | GLColorspace::Linear => true,
+            GLColorspace::BT2020Linear => extensions.gl_colorspace_bt2020_linear(),
+            GLColorspace::BT2020PQ => extensions.gl_colorspace_bt2020_pq(),
+            GLColorspace::BT2020HLG => extensions.gl_colorspace_bt2020_hlg(),
+            GLColorspace::SCRGB => extensions.gl_colorspace_scrgb(),
+            GLColorspace::SCRGBLinear => extensions.gl_colorspace_scrgb_linear(),
+            GLColorspace::DisplayP3 => extensions.gl_colorspace_display_p3(),
+            GLColorspace::DisplayP3Linear => extensions.gl_colorspace_display_p3_linear(),
+        }
+    }
+}
+
+/// Display does not support EGL 1.5, extension EGL_KHR_gl_colorspace
+/// or the extension required by the `GLColorspace`.
+#[derive(Debug)]
+pub struct GLColorspaceNotSupported;
+
+/// Chromaticity coordinates in CIE 1931 color space.
+#[derive(Debug, Copy, Clone)]
+pub struct Chromaticity {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// Mastering display metadata.
+///
+/// Extension EGL_EXT_surface_SMPTE2086_metadata
+#[derive(Debug, Copy, Clone)]
+pub struct SMPTE2086Metadata {
+    pub display_primary_red: Chromaticity,
+    pub display_primary_green: Chromaticity,
+    pub display_primary_blue: Chromaticity,
+    pub white_point: Chromaticity,
+    /// Maximum luminance in cd/m².
+    pub max_luminance: f32,
+    /// Minimum luminance in cd/m².
+    pub min_luminance: f32,
+}
+
+/// Content light level metadata.
+///
+/// Extension EGL_EXT_surface_CTA861_3_metadata
+#[derive(Debug, Copy, Clone)]
+pub struct CTA861_3Metadata {
+    /// Maximum content light level in cd/m².
+    pub max_content_light_level: f32,
+    /// Maximum frame average light level in cd/m².
+    pub max_frame_average_level: f32,
+}
+
+#[derive(Debug, Copy, Clone)]
+#[repr(u32)]
 pub enum SetSurfaceAttribute {
     /// OpenGL ES
     MipmapLevel = ffi::MIPMAP_LEVEL,
 
     MultisampleResolve = ffi::MULTISAMPLE_RESOLVE,
     SwapBehavior = ffi::SWAP_BEHAVIOR,
+
+    /// Extension EGL_EXT_surface_SMPTE2086_metadata
+    SMPTE2086DisplayPrimaryRX = extensions::SMPTE2086_DISPLAY_PRIMARY_RX_EXT,
+    SMPTE2086DisplayPrimaryRY = extensions::SMPTE2086_DISPLAY_PRIMARY_RY_EXT,
+    SMPTE2086DisplayPrimaryGX = extensions::SMPTE2086_DISPLAY_PRIMARY_GX_EXT,
+    SMPTE2086DisplayPrimaryGY = extensions::SMPTE2086_DISPLAY_PRIMARY_GY_EXT,
+    SMPTE2086DisplayPrimaryBX = extensions::SMPTE2086_DISPLAY_PRIMARY_BX_EXT,
+    SMPTE2086DisplayPrimaryBY = extensions::SMPTE2086_DISPLAY_PRIMARY_BY_EXT,
+    SMPTE2086WhitePointX = extensions::SMPTE2086_WHITE_POINT_X_EXT,
+    SMPTE2086WhitePointY = extensions::SMPTE2086_WHITE_POINT_Y_EXT,
+    SMPTE2086MaxLuminance = extensions::SMPTE2086_MAX_LUMINANCE_EXT,
+    SMPTE2086MinLuminance = extensions::SMPTE2086_MIN_LUMINANCE_EXT,
+
+    /// Extension EGL_EXT_surface_CTA861_3_metadata
+    CTA861_3MaxContentLightLevel = extensions::CTA861_3_MAX_CONTENT_LIGHT_LEVEL_EXT,
+    CTA861_3MaxFrameAverageLevel = extensions::CTA861_3_MAX_FRAME_AVERAGE_LEVEL_EXT,
 }
 
 #[derive(Debug)]
@@ -73,9 +193,15 @@ pub enum QueryableAttribute {
     TextureTarget = ffi::TEXTURE_TARGET,
     VerticalResolution = ffi::VERTICAL_RESOLUTION,
     Width = ffi::WIDTH,
+    GLColorspace = ffi::GL_COLORSPACE,
+    /// Extension EGL_EXT_buffer_age or EGL_KHR_partial_update
+    BufferAge = extensions::BUFFER_AGE_EXT,
 }
 
 pub trait SurfaceAttributeUtils: Surface {
+    /// Extensions of the display which the surface was created with.
+    fn display_extensions(&self) -> &DisplayExtensionSupport;
+
     fn set_surface_attribute(
         &mut self,
         attribute: SetSurfaceAttribute,
@@ -279,6 +405,24 @@ pub trait WindowAttributes: SurfaceAttributeUtils {
         self.query_unsigned_integer(QueryableAttribute::PixelAspectRatio)
     }
 
+    /// Number of frames since the current back buffer contents were
+    /// drawn. Zero means that the contents are undefined.
+    ///
+    /// Extension EGL_EXT_buffer_age or EGL_KHR_partial_update. Returns `Ok(None)`
+    /// if neither is supported.
+    ///
+    /// Surface must be current on the calling thread.
+    fn buffer_age(&self) -> QueryResult<Option<UnsignedInteger>> {
+        let extensions = self.display_extensions();
+
+        if !extensions.buffer_age() && !extensions.partial_update() {
+            return Ok(None);
+        }
+
+        self.query_unsigned_integer(QueryableAttribute::BufferAge)
+            .map(Some)
+    }
+
     fn render_buffer(&self) -> QueryResult<RenderBuffer> {
         let value = self.query_attribute(QueryableAttribute::RenderBuffer)?;
 
@@ -290,4 +434,120 @@ pub trait WindowAttributes: SurfaceAttributeUtils {
     }
 }
 
-// TODO: OpenVG surface attribute querying
+/// OpenVG surface attributes.
+pub trait OpenVGAttributes: SurfaceAttributeUtils {
+    fn vg_colorspace(&self) -> QueryResult<VgColorspace> {
+        let value = self.query_attribute(QueryableAttribute::VgColorSpace)?;
+
+        match value as EGLenum {
+            ffi::VG_COLORSPACE_sRGB => Ok(VgColorspace::SRGB),
+            ffi::VG_COLORSPACE_LINEAR => Ok(VgColorspace::Linear),
+            _ => Err(QueryError::EnumError),
+        }
+    }
+
+    fn vg_alpha_format(&self) -> QueryResult<VgAlphaFormat> {
+        let value = self.query_attribute(QueryableAttribute::VgAlphaFormat)?;
+
+        match value as EGLenum {
+            ffi::VG_ALPHA_FORMAT_NONPRE => Ok(VgAlphaFormat::NonPremultiplied),
+            ffi::VG_ALPHA_FORMAT_PRE => Ok(VgAlphaFormat::Premultiplied),
+            _ => Err(QueryError::EnumError),
+        }
+    }
+}
+
+/// OpenGL and OpenGL ES surface attributes.
+///
+/// Requires EGL 1.5 or extension EGL_KHR_gl_colorspace.
+pub trait GLColorspaceAttribute: SurfaceAttributeUtils {
+    fn gl_colorspace(&self) -> QueryResult<GLColorspace> {
+        let value = self.query_attribute(QueryableAttribute::GLColorspace)?;
+
+        match value as EGLenum {
+            ffi::GL_COLORSPACE_SRGB => Ok(GLColorspace::SRGB),
+            ffi::GL_COLORSPACE_LINEAR => Ok(GLColorspace::Linear),
+            extensions::GL_COLORSPACE_BT2020_LINEAR_EXT => Ok(GLColorspace::BT2020Linear),
+            extensions::GL_COLORSPACE_BT2020_PQ_EXT => Ok(GLColorspace::BT2020PQ),
+            extensions::GL_COLORSPACE_BT2020_HLG_EXT => Ok(GLColorspace::BT2020HLG),
+            extensions::GL_COLORSPACE_SCRGB_EXT => Ok(GLColorspace::SCRGB),
+            extensions::GL_COLORSPACE_SCRGB_LINEAR_EXT => Ok(GLColorspace::SCRGBLinear),
+            extensions::GL_COLORSPACE_DISPLAY_P3_EXT => Ok(GLColorspace::DisplayP3),
+            extensions::GL_COLORSPACE_DISPLAY_P3_LINEAR_EXT => Ok(GLColorspace::DisplayP3Linear),
+            _ => Err(QueryError::EnumError),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum HdrMetadataError {
+    /// Display does not support the metadata extension.
+    NotSupported,
+    EGLError(Option<EGLError>),
+}
+
+impl From<Option<EGLError>> for HdrMetadataError {
+    fn from(error: Option<EGLError>) -> Self {
+        HdrMetadataError::EGLError(error)
+    }
+}
+
+/// HDR metadata surface attributes.
+///
+/// Metadata values are converted to integers by multiplying with
+/// `EGL_METADATA_SCALING_EXT`.
+pub trait HdrMetadata: SurfaceAttributeUtils {
+    /// Extension EGL_EXT_surface_SMPTE2086_metadata
+    fn set_smpte2086_metadata(
+        &mut self,
+        metadata: &SMPTE2086Metadata,
+    ) -> Result<(), HdrMetadataError> {
+        if !self.display_extensions().surface_smpte2086_metadata() {
+            return Err(HdrMetadataError::NotSupported);
+        }
+
+        let values = [
+            (SetSurfaceAttribute::SMPTE2086DisplayPrimaryRX, metadata.display_primary_red.x),
+            (SetSurfaceAttribute::SMPTE2086DisplayPrimaryRY, metadata.display_primary_red.y),
+            (SetSurfaceAttribute::SMPTE2086DisplayPrimaryGX, metadata.display_primary_green.x),
+            (SetSurfaceAttribute::SMPTE2086DisplayPrimaryGY, metadata.display_primary_green.y),
+            (SetSurfaceAttribute::SMPTE2086DisplayPrimaryBX, metadata.display_primary_blue.x),
+            (SetSurfaceAttribute::SMPTE2086DisplayPrimaryBY, metadata.display_primary_blue.y),
+            (SetSurfaceAttribute::SMPTE2086WhitePointX, metadata.white_point.x),
+            (SetSurfaceAttribute::SMPTE2086WhitePointY, metadata.white_point.y),
+            (SetSurfaceAttribute::SMPTE2086MaxLuminance, metadata.max_luminance),
+            (SetSurfaceAttribute::SMPTE2086MinLuminance, metadata.min_luminance),
+        ];
+
+        for &(attribute, value) in values.iter() {
+            self.set_surface_attribute(attribute, scale_metadata_value(value))?;
+        }
+
+        Ok(())
+    }
+
+    /// Extension EGL_EXT_surface_CTA861_3_metadata
+    fn set_cta861_3_metadata(
+        &mut self,
+        metadata: &CTA861_3Metadata,
+    ) -> Result<(), HdrMetadataError> {
+        if !self.display_extensions().surface_cta861_3_metadata() {
+            return Err(HdrMetadataError::NotSupported);
+        }
+
+        self.set_surface_attribute(
+            SetSurfaceAttribute::CTA861_3MaxContentLightLevel,
+            scale_metadata_value(metadata.max_content_light_level),
+        )?;
+        self.set_surface_attribute(
+            SetSurfaceAttribute::CTA861_3MaxFrameAverageLevel,
+            scale_metadata_value(metadata.max_frame_average_level),
+        )?;
+
+        Ok(())
+    }
+}
+
+fn scale_metadata_value(value: f32) -> EGLint {
+    (value * extensions::METADATA_SCALING_EXT as f32).round() as EGLint
+}
@@ -1,9 +1,10 @@
 pub mod window;
-//pub mod pbuffer;
-//pub mod pixmap;
+pub mod pbuffer;
+pub mod pixmap;
 pub mod attribute;
 
 use egl_sys::ffi;
+use egl_sys::ffi::types::EGLint;
 
 use EGLHandle;
 use error::EGLError;
@@ -14,6 +15,26 @@ pub trait Surface {
     fn egl_handle(&self) -> &EGLHandle;
 }
 
+/// Rectangle in surface coordinates. Origin is at the bottom left
+/// corner of the surface.
+#[derive(Debug, Copy, Clone)]
+pub struct Rect {
+    pub x: EGLint,
+    pub y: EGLint,
+    pub width: EGLint,
+    pub height: EGLint,
+}
+
+impl Rect {
+    /// Convert rectangles to list of integers `[x, y, width, height, ...]`.
+    pub(crate) fn to_rect_list(rects: &[Rect]) -> Vec<EGLint> {
+        rects
+            .iter()
+            .flat_map(|rect| vec![rect.x, rect.y, rect.width, rect.height])
+            .collect()
+    }
+}
+
 fn destroy_surface<S: Surface>(surface: &mut S) {
     let result =
         unsafe { egl_function!(surface.egl_handle(), DestroySurface(surface.raw_display(), surface.raw_surface())) };
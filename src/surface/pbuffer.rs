@@ -3,62 +3,80 @@ use std::marker::PhantomData;
 use egl_sys::ffi;
 use egl_sys::ffi::types::EGLint;
 
-use config::DisplayConfig;
+use config::client_api::ConfigPbuffer;
+use display::{DisplayExtensionSupport, DisplayType};
+use platform::Platform;
 use utils::{AttributeListBuilder, UnsignedInteger};
 
 use error::EGLError;
+use EGLHandle;
 
 use super::{destroy_surface, Surface};
 
-use super::attribute::{CommonAttributes, LargestPbuffer, MultisampleResolve,
-                       SurfaceAttributeUtils, SwapBehavior, Texture, TextureFormat, TextureTarget};
+use super::attribute::{CommonAttributes, GLColorspace, GLColorspaceAttribute,
+                       GLColorspaceNotSupported, HdrMetadata, LargestPbuffer, MultisampleResolve,
+                       OpenVGAttributes, SurfaceAttributeUtils, SwapBehavior, Texture,
+                       TextureFormat, TextureTarget, VgAlphaFormat, VgColorspace};
 
 #[derive(Debug)]
-pub struct PbufferSurface {
-    display_config: DisplayConfig,
+pub struct PbufferSurface<P: Platform> {
+    pbuffer_config: ConfigPbuffer<P>,
     raw_surface: ffi::types::EGLSurface,
     _marker: PhantomData<ffi::types::EGLSurface>,
 }
 
-impl Surface for PbufferSurface {
+impl<P: Platform> Surface for PbufferSurface<P> {
     fn raw_surface(&self) -> ffi::types::EGLSurface {
         self.raw_surface
     }
 
-    fn display_config(&self) -> &DisplayConfig {
-        &self.display_config
+    fn raw_display(&self) -> ffi::types::EGLDisplay {
+        self.pbuffer_config.display_config().raw_display()
+    }
+
+    fn egl_handle(&self) -> &EGLHandle {
+        self.pbuffer_config.display_config().egl_handle()
     }
 }
 
-impl Drop for PbufferSurface {
+impl<P: Platform> Drop for PbufferSurface<P> {
     fn drop(&mut self) {
         destroy_surface(self)
     }
 }
 
-impl SurfaceAttributeUtils for PbufferSurface {}
-impl CommonAttributes for PbufferSurface {}
-impl MultisampleResolve for PbufferSurface {}
-impl SwapBehavior for PbufferSurface {}
+// Sound for the same reason as `WindowSurface`.
+unsafe impl<P: Platform + Send + Sync> Send for PbufferSurface<P> {}
+
+impl<P: Platform> SurfaceAttributeUtils for PbufferSurface<P> {
+    fn display_extensions(&self) -> &DisplayExtensionSupport {
+        self.pbuffer_config.display_config().display_handle().display_extensions()
+    }
+}
+
+impl<P: Platform> CommonAttributes for PbufferSurface<P> {}
+impl<P: Platform> MultisampleResolve for PbufferSurface<P> {}
+impl<P: Platform> SwapBehavior for PbufferSurface<P> {}
+impl<P: Platform> OpenVGAttributes for PbufferSurface<P> {}
+impl<P: Platform> GLColorspaceAttribute for PbufferSurface<P> {}
+impl<P: Platform> HdrMetadata for PbufferSurface<P> {}
 
-impl Texture for PbufferSurface {}
-impl LargestPbuffer for PbufferSurface {}
+impl<P: Platform> Texture for PbufferSurface<P> {}
+impl<P: Platform> LargestPbuffer for PbufferSurface<P> {}
 
-pub struct PbufferSurfaceBuilder {
-    display_config: DisplayConfig,
+pub struct PbufferSurfaceBuilder<P: Platform> {
+    pbuffer_config: ConfigPbuffer<P>,
     attributes: AttributeListBuilder,
 }
 
-impl PbufferSurfaceBuilder {
-    pub(crate) fn new(display_config: DisplayConfig) -> PbufferSurfaceBuilder {
+impl<P: Platform> PbufferSurfaceBuilder<P> {
+    pub fn new(pbuffer_config: ConfigPbuffer<P>) -> PbufferSurfaceBuilder<P> {
         PbufferSurfaceBuilder {
-            display_config,
+            pbuffer_config,
             attributes: AttributeListBuilder::new(),
         }
     }
 
-    // TODO: PbufferSurface OpenVG attributes
-
     /// Default value: zero
     pub fn width(&mut self, width: UnsignedInteger) -> &mut Self {
         self.attributes.add(ffi::WIDTH as EGLint, width.value());
@@ -106,23 +124,63 @@ impl PbufferSurfaceBuilder {
         self
     }
 
-    pub fn build(self) -> Result<PbufferSurface, Option<EGLError>> {
+    /// OpenVG
+    ///
+    /// Default value: `VgColorspace::SRGB`
+    pub fn vg_colorspace(&mut self, colorspace: VgColorspace) -> &mut Self {
+        self.attributes
+            .add(ffi::VG_COLORSPACE as EGLint, colorspace as EGLint);
+        self
+    }
+
+    /// OpenVG
+    ///
+    /// Default value: `VgAlphaFormat::NonPremultiplied`
+    pub fn vg_alpha_format(&mut self, alpha_format: VgAlphaFormat) -> &mut Self {
+        self.attributes
+            .add(ffi::VG_ALPHA_FORMAT as EGLint, alpha_format as EGLint);
+        self
+    }
+
+    /// OpenGL and OpenGL ES
+    ///
+    /// Default value: `GLColorspace::Linear`
+    ///
+    /// Support for the colorspace is checked from the display of
+    /// the config of this builder.
+    pub fn gl_colorspace(
+        &mut self,
+        colorspace: GLColorspace,
+    ) -> Result<&mut Self, GLColorspaceNotSupported> {
+        if !colorspace.supported(self.pbuffer_config.display_config().display_handle()) {
+            return Err(GLColorspaceNotSupported);
+        }
+
+        self.attributes
+            .add(ffi::GL_COLORSPACE as EGLint, colorspace as EGLint);
+        Ok(self)
+    }
+
+    pub fn build(self) -> Result<PbufferSurface<P>, Option<EGLError>> {
         let attributes = self.attributes.build();
 
         let result = unsafe {
-            ffi::CreatePbufferSurface(
-                self.display_config.raw_display(),
-                self.display_config.raw_config(),
-                attributes.ptr(),
+            egl_function!(
+                self.pbuffer_config.egl_handle(),
+                CreatePbufferSurface(
+                    self.pbuffer_config.display_config().raw_display(),
+                    self.pbuffer_config.display_config().raw_config(),
+                    attributes.ptr()
+                )
             )
         };
 
         if result == ffi::NO_SURFACE {
-            return Err(EGLError::check_errors());
+            return Err(EGLError::check_errors(self.pbuffer_config.egl_handle()));
         }
 
         Ok(PbufferSurface {
-            display_config: self.display_config,
+            pbuffer_config: self.pbuffer_config,
             raw_surface: result,
             _marker: PhantomData,
         })
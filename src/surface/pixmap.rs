@@ -1,85 +1,130 @@
 use std::marker::PhantomData;
 
 use egl_sys::ffi;
+use egl_sys::ffi::types::EGLint;
 
-use config::DisplayConfig;
-use utils::AttributeListBuilder;
-
-use error::EGLError;
+use utils::{AttributeList, AttributeListBuilder, AttributeListTrait};
+use config::client_api::ConfigPixmap;
+use display::{DisplayExtensionSupport, DisplayType};
+use platform::Platform;
+use EGLHandle;
 
 use super::{destroy_surface, Surface};
 
-use super::attribute::{CommonAttributes, MultisampleResolve, SurfaceAttributeUtils, SwapBehavior};
+use super::attribute::{CommonAttributes, MultisampleResolve, OpenVGAttributes, SurfaceAttributeUtils,
+                       SwapBehavior, VgAlphaFormat, VgColorspace};
 
 #[derive(Debug)]
-pub struct PixmapSurface {
-    display_config: DisplayConfig,
+pub struct PixmapSurface<T, P: Platform> {
+    optional_native_pixmap_handle: T,
+    pixmap_config: ConfigPixmap<P>,
     raw_surface: ffi::types::EGLSurface,
     _marker: PhantomData<ffi::types::EGLSurface>,
 }
 
-impl Surface for PixmapSurface {
+impl<T, P: Platform> PixmapSurface<T, P> {
+    pub(crate) fn new(
+        optional_native_pixmap_handle: T,
+        pixmap_config: ConfigPixmap<P>,
+        raw_surface: ffi::types::EGLSurface,
+    ) -> Self {
+        PixmapSurface {
+            optional_native_pixmap_handle,
+            pixmap_config,
+            raw_surface,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn optional_native_pixmap_handle(&self) -> &T {
+        &self.optional_native_pixmap_handle
+    }
+
+    pub fn optional_native_pixmap_handle_mut(&mut self) -> &mut T {
+        &mut self.optional_native_pixmap_handle
+    }
+}
+
+impl<T, P: Platform> Surface for PixmapSurface<T, P> {
     fn raw_surface(&self) -> ffi::types::EGLSurface {
         self.raw_surface
     }
 
-    fn display_config(&self) -> &DisplayConfig {
-        &self.display_config
+    fn raw_display(&self) -> ffi::types::EGLDisplay {
+        self.pixmap_config.display_config().raw_display()
+    }
+
+    fn egl_handle(&self) -> &EGLHandle {
+        self.pixmap_config.display_config().egl_handle()
     }
 }
 
-impl Drop for PixmapSurface {
+impl<T, P: Platform> Drop for PixmapSurface<T, P> {
     fn drop(&mut self) {
         destroy_surface(self)
     }
 }
 
-impl SurfaceAttributeUtils for PixmapSurface {}
-impl CommonAttributes for PixmapSurface {}
-impl MultisampleResolve for PixmapSurface {}
-impl SwapBehavior for PixmapSurface {}
+// Sound for the same reason as `WindowSurface`. Native pixmap must be `Send`.
+unsafe impl<T: Send, P: Platform + Send + Sync> Send for PixmapSurface<T, P> {}
+
+impl<T, P: Platform> SurfaceAttributeUtils for PixmapSurface<T, P> {
+    fn display_extensions(&self) -> &DisplayExtensionSupport {
+        self.pixmap_config.display_config().display_handle().display_extensions()
+    }
+}
 
-pub struct PixmapSurfaceBuilder {
-    display_config: DisplayConfig,
+impl<T, P: Platform> CommonAttributes for PixmapSurface<T, P> {}
+impl<T, P: Platform> MultisampleResolve for PixmapSurface<T, P> {}
+impl<T, P: Platform> SwapBehavior for PixmapSurface<T, P> {}
+impl<T, P: Platform> OpenVGAttributes for PixmapSurface<T, P> {}
+
+pub struct PixmapSurfaceAttributeListBuilder {
     attributes: AttributeListBuilder,
-    native_pixmap: ffi::types::EGLNativePixmapType,
 }
 
-impl PixmapSurfaceBuilder {
-    pub(crate) fn new(
-        display_config: DisplayConfig,
-        native_pixmap: ffi::types::EGLNativePixmapType,
-    ) -> PixmapSurfaceBuilder {
-        PixmapSurfaceBuilder {
-            display_config,
+impl PixmapSurfaceAttributeListBuilder {
+    pub fn new() -> Self {
+        PixmapSurfaceAttributeListBuilder {
             attributes: AttributeListBuilder::new(),
-            native_pixmap,
         }
     }
 
     // TODO: search configs with MatchNativePixmap if creating pixmap surface
-    // TODO: PixmapSurface OpenVG attributes
-
-    pub fn build(self) -> Result<PixmapSurface, Option<EGLError>> {
-        let attributes = self.attributes.build();
-
-        let result = unsafe {
-            ffi::CreatePixmapSurface(
-                self.display_config.raw_display(),
-                self.display_config.raw_config(),
-                self.native_pixmap,
-                attributes.ptr(),
-            )
-        };
-
-        if result == ffi::NO_SURFACE {
-            return Err(EGLError::check_errors());
-        }
 
-        Ok(PixmapSurface {
-            display_config: self.display_config,
-            raw_surface: result,
-            _marker: PhantomData,
-        })
+    /// OpenVG
+    ///
+    /// Default value: `VgColorspace::SRGB`
+    pub fn vg_colorspace(&mut self, colorspace: VgColorspace) -> &mut Self {
+        self.attributes
+            .add(ffi::VG_COLORSPACE as EGLint, colorspace as EGLint);
+        self
+    }
+
+    /// OpenVG
+    ///
+    /// Default value: `VgAlphaFormat::NonPremultiplied`
+    pub fn vg_alpha_format(&mut self, alpha_format: VgAlphaFormat) -> &mut Self {
+        self.attributes
+            .add(ffi::VG_ALPHA_FORMAT as EGLint, alpha_format as EGLint);
+        self
+    }
+
+    pub fn build(self) -> PixmapSurfaceAttributeList {
+        PixmapSurfaceAttributeList(self.attributes.build())
+    }
+}
+
+pub struct PixmapSurfaceAttributeList(AttributeList);
+
+impl PixmapSurfaceAttributeList {
+    pub fn ptr(&self) -> *const EGLint {
+        self.0.attribute_list_ptr()
+    }
+}
+
+impl Default for PixmapSurfaceAttributeList {
+    fn default() -> Self {
+        PixmapSurfaceAttributeList(AttributeList::empty())
     }
 }
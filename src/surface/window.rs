@@ -5,13 +5,16 @@ use egl_sys::ffi::types::EGLint;
 
 use utils::{AttributeList, AttributeListBuilder, AttributeListTrait};
 use config::client_api::ConfigWindow;
+use display::{DisplayExtensionSupport, DisplayType};
 use platform::Platform;
 use EGLHandle;
 
 use super::{destroy_surface, Surface};
 
-use super::attribute::{CommonAttributes, MultisampleResolve, RenderBuffer, SurfaceAttributeUtils,
-                       SwapBehavior, WindowAttributes};
+use super::attribute::{CommonAttributes, GLColorspace, GLColorspaceAttribute,
+                       GLColorspaceNotSupported, HdrMetadata, MultisampleResolve,
+                       OpenVGAttributes, RenderBuffer, SurfaceAttributeUtils, SwapBehavior,
+                       VgAlphaFormat, VgColorspace, WindowAttributes};
 
 #[derive(Debug)]
 pub struct WindowSurface<T, P: Platform> {
@@ -64,10 +67,22 @@ impl<T, P: Platform> Drop for WindowSurface<T, P> {
     }
 }
 
-impl<T, P: Platform> SurfaceAttributeUtils for WindowSurface<T, P> {}
+// A current surface is owned by a `!Send` current context type, so only
+// surfaces which are not current can be moved. Native window must be `Send`.
+unsafe impl<T: Send, P: Platform + Send + Sync> Send for WindowSurface<T, P> {}
+
+impl<T, P: Platform> SurfaceAttributeUtils for WindowSurface<T, P> {
+    fn display_extensions(&self) -> &DisplayExtensionSupport {
+        self.window_config.display_config().display_handle().display_extensions()
+    }
+}
+
 impl<T, P: Platform> CommonAttributes for WindowSurface<T, P> {}
 impl<T, P: Platform> MultisampleResolve for WindowSurface<T, P> {}
 impl<T, P: Platform> SwapBehavior for WindowSurface<T, P> {}
+impl<T, P: Platform> OpenVGAttributes for WindowSurface<T, P> {}
+impl<T, P: Platform> GLColorspaceAttribute for WindowSurface<T, P> {}
+impl<T, P: Platform> HdrMetadata for WindowSurface<T, P> {}
 
 impl<T, P: Platform> WindowAttributes for WindowSurface<T, P> {}
 
@@ -82,7 +97,43 @@ impl WindowSurfaceAttributeListBuilder {
         }
     }
 
-    // TODO: WindowSurface OpenVG attributes
+    /// OpenVG
+    ///
+    /// Default value: `VgColorspace::SRGB`
+    pub fn vg_colorspace(&mut self, colorspace: VgColorspace) -> &mut Self {
+        self.attributes
+            .add(ffi::VG_COLORSPACE as EGLint, colorspace as EGLint);
+        self
+    }
+
+    /// OpenVG
+    ///
+    /// Default value: `VgAlphaFormat::NonPremultiplied`
+    pub fn vg_alpha_format(&mut self, alpha_format: VgAlphaFormat) -> &mut Self {
+        self.attributes
+            .add(ffi::VG_ALPHA_FORMAT as EGLint, alpha_format as EGLint);
+        self
+    }
+
+    /// OpenGL and OpenGL ES
+    ///
+    /// Default value: `GLColorspace::Linear`
+    ///
+    /// Support for the colorspace is checked from the display of
+    /// `config_window`. Use the same config when creating the surface.
+    pub fn gl_colorspace<P: Platform>(
+        &mut self,
+        config_window: &ConfigWindow<P>,
+        colorspace: GLColorspace,
+    ) -> Result<&mut Self, GLColorspaceNotSupported> {
+        if !colorspace.supported(config_window.display_config().display_handle()) {
+            return Err(GLColorspaceNotSupported);
+        }
+
+        self.attributes
+            .add(ffi::GL_COLORSPACE as EGLint, colorspace as EGLint);
+        Ok(self)
+    }
 
     /// Set preferred rendering buffer.
     ///
@@ -0,0 +1,332 @@
+//! Sync objects.
+//!
+//! Uses EGL 1.5 functions if display supports EGL 1.5, otherwise
+//! extension EGL_KHR_fence_sync is required.
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
+
+use egl_sys::ffi;
+use egl_sys::ffi::types::{EGLAttrib, EGLenum, EGLint};
+use egl_sys::extensions;
+
+use display::{DisplayHandle, DisplayType, EGLVersion};
+use error::EGLError;
+use platform::Platform;
+use utils::{AttributeList, QueryError, QueryResult};
+use EGLHandle;
+
+#[cfg(unix)]
+use std::os::unix::io::FromRawFd;
+#[cfg(unix)]
+use utils::OwnedFd;
+
+#[derive(Debug, Copy, Clone)]
+enum SyncFunctions {
+    EGL15,
+    KHR,
+}
+
+#[derive(Debug)]
+pub enum SyncCreationError {
+    /// EGL 1.5 or required extension is not supported.
+    NotSupported,
+    EGLError(Option<EGLError>),
+}
+
+#[derive(Debug)]
+pub enum WaitSyncError {
+    /// EGL 1.5 or extension EGL_KHR_wait_sync is not supported.
+    NotSupported,
+    /// Sync object and context are not from the same display.
+    DifferentDisplay,
+    EGLError(Option<EGLError>),
+}
+
+#[cfg(unix)]
+#[derive(Debug)]
+pub enum NativeFenceError {
+    /// Extension EGL_ANDROID_native_fence_sync is not supported.
+    NotSupported,
+    EGLError(Option<EGLError>),
+}
+
+/// Result of `EGLSync::client_wait`.
+#[derive(Debug, PartialEq)]
+pub enum SyncWaitResult {
+    Signaled,
+    TimeoutExpired,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SyncStatus {
+    Signaled,
+    Unsignaled,
+}
+
+/// Sync object which will be destroyed when dropped.
+///
+/// Sync object has reference counted handle to the display, so display
+/// will not be terminated before sync object is dropped.
+#[derive(Debug)]
+pub struct EGLSync<P: Platform> {
+    display_handle: Arc<DisplayHandle<P>>,
+    raw_sync: ffi::types::EGLSync,
+    functions: SyncFunctions,
+    _marker: PhantomData<ffi::types::EGLSync>,
+}
+
+// Sync objects can be used from multiple threads.
+unsafe impl<P: Platform + Send + Sync> Send for EGLSync<P> {}
+unsafe impl<P: Platform + Send + Sync> Sync for EGLSync<P> {}
+
+impl<P: Platform> EGLSync<P> {
+    pub(crate) fn new(
+        display_handle: &Arc<DisplayHandle<P>>,
+        sync_type: EGLenum,
+        attribute_list: AttributeList,
+    ) -> Result<Self, SyncCreationError> {
+        let functions = match display_handle.egl_version() {
+            EGLVersion::EGL_1_5 => SyncFunctions::EGL15,
+            EGLVersion::EGL_1_4 if display_handle.display_extensions().fence_sync() => {
+                SyncFunctions::KHR
+            }
+            EGLVersion::EGL_1_4 => return Err(SyncCreationError::NotSupported),
+        };
+
+        let egl_handle = DisplayHandle::egl_handle(display_handle);
+        let raw_display = DisplayHandle::raw_display(display_handle);
+
+        let raw_sync = match functions {
+            SyncFunctions::EGL15 => {
+                let attribute_list = attribute_list.to_attrib_list();
+
+                unsafe {
+                    egl_function!(
+                        egl_handle,
+                        CreateSync(raw_display, sync_type, attribute_list.as_ptr())
+                    )
+                }
+            }
+            SyncFunctions::KHR => unsafe {
+                egl_handle.functions.extensions.CreateSyncKHR(
+                    raw_display,
+                    sync_type,
+                    attribute_list.ptr(),
+                )
+            },
+        };
+
+        if raw_sync == ffi::NO_SYNC {
+            return Err(SyncCreationError::EGLError(EGLError::check_errors(egl_handle)));
+        }
+
+        Ok(EGLSync {
+            display_handle: display_handle.clone(),
+            raw_sync,
+            functions,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn raw_sync(&self) -> ffi::types::EGLSync {
+        self.raw_sync
+    }
+
+    pub fn raw_display(&self) -> ffi::types::EGLDisplay {
+        self.display_handle.raw_display()
+    }
+
+    pub fn display_handle(&self) -> &Arc<DisplayHandle<P>> {
+        &self.display_handle
+    }
+
+    fn egl_handle(&self) -> &EGLHandle {
+        self.display_handle.egl_handle()
+    }
+
+    /// Block calling thread until sync object is signaled or timeout expires.
+    ///
+    /// If `timeout` is `None`, wait forever.
+    ///
+    /// If `flush` is true, commands of the current context are flushed before
+    /// waiting. This prevents waiting forever for a fence which is not yet
+    /// submitted for execution.
+    pub fn client_wait(
+        &self,
+        flush: bool,
+        timeout: Option<Duration>,
+    ) -> Result<SyncWaitResult, Option<EGLError>> {
+        let flags = if flush {
+            ffi::SYNC_FLUSH_COMMANDS_BIT as EGLint
+        } else {
+            0
+        };
+
+        let timeout = match timeout {
+            Some(duration) => duration
+                .as_secs()
+                .saturating_mul(1_000_000_000)
+                .saturating_add(duration.subsec_nanos() as u64),
+            None => ffi::FOREVER as u64,
+        };
+
+        let result = match self.functions {
+            SyncFunctions::EGL15 => unsafe {
+                egl_function!(
+                    self.egl_handle(),
+                    ClientWaitSync(
+                        self.raw_display(),
+                        self.raw_sync,
+                        flags,
+                        timeout as ffi::types::EGLTime
+                    )
+                )
+            },
+            SyncFunctions::KHR => unsafe {
+                self.egl_handle().functions.extensions.ClientWaitSyncKHR(
+                    self.raw_display(),
+                    self.raw_sync,
+                    flags,
+                    timeout as extensions::types::EGLTimeKHR,
+                )
+            },
+        };
+
+        match result as EGLenum {
+            ffi::CONDITION_SATISFIED => Ok(SyncWaitResult::Signaled),
+            ffi::TIMEOUT_EXPIRED => Ok(SyncWaitResult::TimeoutExpired),
+            _ => Err(EGLError::check_errors(self.egl_handle())),
+        }
+    }
+
+    /// Make server wait until sync object is signaled. This function
+    /// does not block the calling thread.
+    ///
+    /// Context of this display must be current on the calling thread.
+    pub(crate) fn server_wait(&self) -> Result<(), WaitSyncError> {
+        let result = match self.functions {
+            SyncFunctions::EGL15 => unsafe {
+                egl_function!(self.egl_handle(), WaitSync(self.raw_display(), self.raw_sync, 0))
+            },
+            SyncFunctions::KHR => {
+                if !self.display_handle.display_extensions().wait_sync() {
+                    return Err(WaitSyncError::NotSupported);
+                }
+
+                let result = unsafe {
+                    self.egl_handle().functions.extensions.WaitSyncKHR(
+                        self.raw_display(),
+                        self.raw_sync,
+                        0,
+                    )
+                };
+
+                result as ffi::types::EGLBoolean
+            }
+        };
+
+        if result == ffi::TRUE {
+            Ok(())
+        } else {
+            Err(WaitSyncError::EGLError(EGLError::check_errors(self.egl_handle())))
+        }
+    }
+
+    /// Extension EGL_ANDROID_native_fence_sync
+    ///
+    /// Duplicate file descriptor of native fence sync object. The caller
+    /// owns the returned file descriptor.
+    ///
+    /// Native fence sync object created with
+    /// `Display::create_native_fence_sync` has a file descriptor only
+    /// after commands of the context are flushed.
+    #[cfg(unix)]
+    pub fn dup_native_fence_fd(&self) -> Result<OwnedFd, NativeFenceError> {
+        if !self.display_handle.display_extensions().android_native_fence_sync() {
+            return Err(NativeFenceError::NotSupported);
+        }
+
+        let fd = unsafe {
+            self.egl_handle()
+                .functions
+                .extensions
+                .DupNativeFenceFDANDROID(self.raw_display(), self.raw_sync)
+        };
+
+        // Error value EGL_NO_NATIVE_FENCE_FD_ANDROID is -1.
+        if fd < 0 {
+            Err(NativeFenceError::EGLError(EGLError::check_errors(self.egl_handle())))
+        } else {
+            Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+        }
+    }
+
+    pub fn status(&self) -> QueryResult<SyncStatus> {
+        let value = self.query_attribute(ffi::SYNC_STATUS as EGLint)?;
+
+        match value as EGLenum {
+            ffi::SIGNALED => Ok(SyncStatus::Signaled),
+            ffi::UNSIGNALED => Ok(SyncStatus::Unsignaled),
+            _ => Err(QueryError::EnumError),
+        }
+    }
+
+    fn query_attribute(&self, attribute: EGLint) -> QueryResult<EGLAttrib> {
+        let result = match self.functions {
+            SyncFunctions::EGL15 => {
+                let mut value: EGLAttrib = 0;
+
+                let result = unsafe {
+                    egl_function!(
+                        self.egl_handle(),
+                        GetSyncAttrib(self.raw_display(), self.raw_sync, attribute, &mut value)
+                    )
+                };
+
+                (result, value)
+            }
+            SyncFunctions::KHR => {
+                let mut value: EGLint = 0;
+
+                let result = unsafe {
+                    self.egl_handle().functions.extensions.GetSyncAttribKHR(
+                        self.raw_display(),
+                        self.raw_sync,
+                        attribute,
+                        &mut value,
+                    )
+                };
+
+                (result, value as EGLAttrib)
+            }
+        };
+
+        match result {
+            (ffi::TRUE, value) => Ok(value),
+            _ => Err(QueryError::QueryError),
+        }
+    }
+}
+
+impl<P: Platform> Drop for EGLSync<P> {
+    fn drop(&mut self) {
+        let result = match self.functions {
+            SyncFunctions::EGL15 => unsafe {
+                egl_function!(self.egl_handle(), DestroySync(self.raw_display(), self.raw_sync))
+            },
+            SyncFunctions::KHR => unsafe {
+                self.egl_handle()
+                    .functions
+                    .extensions
+                    .DestroySyncKHR(self.raw_display(), self.raw_sync)
+            },
+        };
+
+        if result == ffi::FALSE {
+            let error = EGLError::check_errors(self.egl_handle());
+            eprintln!("egl_wrapper: couldn't destroy sync object, error: {:?}", error);
+        }
+    }
+}
@@ -1,6 +1,13 @@
 use std::fmt;
 
-use egl_sys::ffi::types::EGLint;
+#[cfg(unix)]
+use std::fs::File;
+#[cfg(unix)]
+use std::mem;
+#[cfg(unix)]
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+
+use egl_sys::ffi::types::{EGLAttrib, EGLint};
 use egl_sys::ffi;
 
 #[derive(Debug, Copy, Clone)]
@@ -105,6 +112,19 @@ impl AttributeList {
     pub(crate) fn empty() -> Self {
         AttributeList(vec![ffi::NONE as EGLint])
     }
+
+    /// Add attribute to the end of the list.
+    pub(crate) fn add(&mut self, attribute: EGLint, value: EGLint) {
+        self.0.pop();
+        self.0.push(attribute);
+        self.0.push(value);
+        self.0.push(ffi::NONE as EGLint);
+    }
+
+    /// Convert to attribute list for EGL 1.5 functions.
+    pub(crate) fn to_attrib_list(&self) -> Vec<EGLAttrib> {
+        self.0.iter().map(|value| *value as EGLAttrib).collect()
+    }
 }
 
 impl AttributeListTrait for AttributeList {
@@ -136,3 +156,45 @@ impl From<IntegerError> for QueryError {
 }
 
 pub type QueryResult<T> = Result<T, QueryError>;
+
+/// File descriptor which is closed when dropped.
+///
+/// Use `IntoRawFd::into_raw_fd` to release the ownership of the file
+/// descriptor without closing it.
+#[cfg(unix)]
+#[derive(Debug)]
+pub struct OwnedFd(RawFd);
+
+#[cfg(unix)]
+impl FromRawFd for OwnedFd {
+    /// Takes ownership of the file descriptor.
+    unsafe fn from_raw_fd(fd: RawFd) -> Self {
+        OwnedFd(fd)
+    }
+}
+
+#[cfg(unix)]
+impl AsRawFd for OwnedFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0
+    }
+}
+
+#[cfg(unix)]
+impl IntoRawFd for OwnedFd {
+    fn into_raw_fd(self) -> RawFd {
+        let fd = self.0;
+        mem::forget(self);
+        fd
+    }
+}
+
+#[cfg(unix)]
+impl Drop for OwnedFd {
+    fn drop(&mut self) {
+        // File closes the file descriptor when dropped.
+        unsafe {
+            File::from_raw_fd(self.0);
+        }
+    }
+}
@@ -0,0 +1,229 @@
+//! Native Wayland windows for EGL platform EGL_EXT_platform_wayland.
+//!
+//! Functions from `libwayland-egl` are loaded at runtime if feature
+//! `runtime-linking` is enabled, otherwise the library is linked
+//! at build time.
+
+use std::os::raw::{c_int, c_void};
+use std::sync::Arc;
+use std::fmt;
+
+#[cfg(feature = "runtime-linking")]
+use std::io;
+
+#[cfg(feature = "runtime-linking")]
+use libloading;
+
+use config::client_api::ConfigWindow;
+use platform::{EXTPlatform, WindowCreationError};
+use surface::window::{WindowSurface, WindowSurfaceAttributeList};
+
+/// Opaque type for `struct wl_surface`.
+#[allow(non_camel_case_types)]
+pub enum wl_surface {}
+
+/// Opaque type for `struct wl_egl_window`.
+#[allow(non_camel_case_types)]
+pub enum wl_egl_window {}
+
+type CreateFn = unsafe extern "C" fn(*mut wl_surface, c_int, c_int) -> *mut wl_egl_window;
+type DestroyFn = unsafe extern "C" fn(*mut wl_egl_window);
+type ResizeFn = unsafe extern "C" fn(*mut wl_egl_window, c_int, c_int, c_int, c_int);
+type GetAttachedSizeFn = unsafe extern "C" fn(*mut wl_egl_window, *mut c_int, *mut c_int);
+
+#[cfg(not(feature = "runtime-linking"))]
+#[link(name = "wayland-egl")]
+extern "C" {
+    fn wl_egl_window_create(
+        surface: *mut wl_surface,
+        width: c_int,
+        height: c_int,
+    ) -> *mut wl_egl_window;
+    fn wl_egl_window_destroy(egl_window: *mut wl_egl_window);
+    fn wl_egl_window_resize(
+        egl_window: *mut wl_egl_window,
+        width: c_int,
+        height: c_int,
+        dx: c_int,
+        dy: c_int,
+    );
+    fn wl_egl_window_get_attached_size(
+        egl_window: *mut wl_egl_window,
+        width: *mut c_int,
+        height: *mut c_int,
+    );
+}
+
+#[cfg(all(unix, feature = "runtime-linking"))]
+const WAYLAND_EGL_LIBRARY_NAME: &'static str = "libwayland-egl.so.1";
+
+#[derive(Debug)]
+pub enum WaylandEGLLoadError {
+    /// This error can only happen if runtime library
+    /// loading feature is enabled.
+    #[cfg(feature = "runtime-linking")]
+    LibraryLoadingError(io::Error),
+    /// This error can only happen if runtime library
+    /// loading feature is enabled.
+    #[cfg(feature = "runtime-linking")]
+    SymbolNotFound(io::Error),
+}
+
+struct WaylandEGLFunctions {
+    #[cfg(feature = "runtime-linking")]
+    _library: libloading::Library,
+    create: CreateFn,
+    destroy: DestroyFn,
+    resize: ResizeFn,
+    get_attached_size: GetAttachedSizeFn,
+}
+
+/// Handle to `libwayland-egl` functions.
+#[derive(Clone)]
+pub struct WaylandEGL {
+    functions: Arc<WaylandEGLFunctions>,
+}
+
+impl fmt::Debug for WaylandEGL {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "WaylandEGL")
+    }
+}
+
+impl WaylandEGL {
+    #[cfg(not(feature = "runtime-linking"))]
+    pub fn load() -> Result<Self, WaylandEGLLoadError> {
+        let functions = WaylandEGLFunctions {
+            create: wl_egl_window_create,
+            destroy: wl_egl_window_destroy,
+            resize: wl_egl_window_resize,
+            get_attached_size: wl_egl_window_get_attached_size,
+        };
+
+        Ok(WaylandEGL {
+            functions: Arc::new(functions),
+        })
+    }
+
+    #[cfg(feature = "runtime-linking")]
+    pub fn load() -> Result<Self, WaylandEGLLoadError> {
+        let library = libloading::Library::new(WAYLAND_EGL_LIBRARY_NAME)
+            .map_err(WaylandEGLLoadError::LibraryLoadingError)?;
+
+        let functions = unsafe {
+            let create = *library
+                .get::<CreateFn>(b"wl_egl_window_create\0")
+                .map_err(WaylandEGLLoadError::SymbolNotFound)?;
+            let destroy = *library
+                .get::<DestroyFn>(b"wl_egl_window_destroy\0")
+                .map_err(WaylandEGLLoadError::SymbolNotFound)?;
+            let resize = *library
+                .get::<ResizeFn>(b"wl_egl_window_resize\0")
+                .map_err(WaylandEGLLoadError::SymbolNotFound)?;
+            let get_attached_size = *library
+                .get::<GetAttachedSizeFn>(b"wl_egl_window_get_attached_size\0")
+                .map_err(WaylandEGLLoadError::SymbolNotFound)?;
+
+            WaylandEGLFunctions {
+                _library: library,
+                create,
+                destroy,
+                resize,
+                get_attached_size,
+            }
+        };
+
+        Ok(WaylandEGL {
+            functions: Arc::new(functions),
+        })
+    }
+}
+
+/// Native window `wl_egl_window` which will be destroyed when dropped.
+///
+/// Give the window to `EXTPlatform::get_wayland_window_surface`, so that
+/// `WindowSurface` owns the window and destroys it after the EGL surface.
+/// EGL destroys a current surface only when it is released, so release
+/// the surface with `make_not_current` before dropping it.
+#[derive(Debug)]
+pub struct WaylandEGLWindow {
+    raw_window: *mut wl_egl_window,
+    wayland_egl: WaylandEGL,
+}
+
+impl WaylandEGLWindow {
+    /// Returns `None` if window creation fails.
+    ///
+    /// # Safety
+    ///
+    /// Argument `surface` must be a valid `wl_surface` which is not
+    /// destroyed before the window is dropped.
+    pub unsafe fn new(
+        wayland_egl: &WaylandEGL,
+        surface: *mut wl_surface,
+        width: i32,
+        height: i32,
+    ) -> Option<Self> {
+        let raw_window = (wayland_egl.functions.create)(surface, width, height);
+
+        if raw_window.is_null() {
+            None
+        } else {
+            Some(WaylandEGLWindow {
+                raw_window,
+                wayland_egl: wayland_egl.clone(),
+            })
+        }
+    }
+
+    pub fn raw_window(&self) -> *mut wl_egl_window {
+        self.raw_window
+    }
+
+    /// Resize the window. Arguments `dx` and `dy` are the position of the
+    /// new buffer relative to the current buffer.
+    ///
+    /// New size will be used after the next buffer swap.
+    pub fn resize(&mut self, width: i32, height: i32, dx: i32, dy: i32) {
+        unsafe { (self.wayland_egl.functions.resize)(self.raw_window, width, height, dx, dy) }
+    }
+
+    /// Size of the buffer which is currently attached to the `wl_surface`.
+    pub fn attached_size(&self) -> (i32, i32) {
+        let mut width = 0;
+        let mut height = 0;
+
+        unsafe {
+            (self.wayland_egl.functions.get_attached_size)(self.raw_window, &mut width, &mut height)
+        };
+
+        (width, height)
+    }
+}
+
+impl Drop for WaylandEGLWindow {
+    fn drop(&mut self) {
+        unsafe { (self.wayland_egl.functions.destroy)(self.raw_window) }
+    }
+}
+
+impl<T> EXTPlatform<T> {
+    /// Create window surface from `WaylandEGLWindow`. Returned `WindowSurface`
+    /// owns the window, so release the surface with `make_not_current`
+    /// before dropping it.
+    ///
+    /// # Safety
+    ///
+    /// Display must be created with `EXTPlatformType::Wayland` and from the
+    /// same `wl_display` as the `wl_surface` of the window.
+    pub unsafe fn get_wayland_window_surface(
+        &self,
+        window: WaylandEGLWindow,
+        config_window: ConfigWindow<Self>,
+        attribute_list: WindowSurfaceAttributeList,
+    ) -> Result<WindowSurface<WaylandEGLWindow, Self>, WindowCreationError> {
+        let raw_window = window.raw_window() as *mut c_void;
+
+        self.get_platform_window_surface(window, raw_window, config_window, attribute_list)
+    }
+}